@@ -14,6 +14,9 @@ use ffxiv_model::{BodyId, Character, Context, Customization, Equipment, ModelPar
 pub struct Content {
     window: Window,
     world: World,
+    context: Context,
+    character: Character,
+    start_time: f64,
 }
 
 impl Content {
@@ -39,13 +42,24 @@ impl Content {
         equipments.insert(ModelPart::Dwn, Equipment::new(6016, 1, 0));
         equipments.insert(ModelPart::Sho, Equipment::new(6016, 1, 0));
 
-        let customization = Customization::new(BodyId::AuRaFemale, 1, 1, 1, 1, 1);
-        Character::load(&mut world, &package, &context, customization, equipments).await.unwrap();
+        let customization = Customization::new(BodyId::AuRaFemale, 1, 1, 1, 1, 1).with_tail(1);
+        let character = Character::load(&mut world, &package, &context, customization, equipments).await.unwrap();
 
-        Self { window, world }
+        Self {
+            window,
+            world,
+            context,
+            character,
+            start_time: js_sys::Date::now(),
+        }
     }
 
     pub fn redraw(&mut self) {
+        // milliseconds since start
+        let time = (js_sys::Date::now() - self.start_time) as f32;
+        self.context.update_time(time);
+        self.character.update(time);
+
         let mut renderer = self.world.take_resource::<Renderer>().unwrap();
 
         renderer.render_world(&self.world);
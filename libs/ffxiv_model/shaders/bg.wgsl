@@ -0,0 +1,50 @@
+@group(0) @binding(10)
+var textureSampler: sampler;
+@group(0) @binding(11)
+var diffuse_tex: texture_2d<f32>;
+@group(0) @binding(12)
+var diffuse1_tex: texture_2d<f32>;
+@group(0) @binding(13)
+var normal_tex: texture_2d<f32>;
+@group(0) @binding(14)
+var normal1_tex: texture_2d<f32>;
+@group(0) @binding(15)
+var specular_tex: texture_2d<f32>;
+
+struct bg_param {
+    // dye color of bgcolorchange, white otherwise
+    color: vec4<f32>,
+    // xy, zw: uv scroll per second of each layer of bguvscroll
+    uv_scroll: vec4<f32>,
+};
+@group(0) @binding(20)
+var<uniform> bg: bg_param;
+
+struct scene_param {
+    // x: time in seconds
+    time: vec4<f32>,
+};
+@group(0) @binding(21)
+var<uniform> scene: scene_param;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var tex_coord: vec2<f32> = in.tex_coord + bg.uv_scroll.xy * scene.time.x;
+    var tex_coord1: vec2<f32> = in.tex_coord + bg.uv_scroll.zw * scene.time.x;
+
+    // vertex color alpha blends between two layers
+    var blend: f32 = in.color.a;
+    var diffuse_map: vec4<f32> = mix(textureSample(diffuse_tex, textureSampler, tex_coord), textureSample(diffuse1_tex, textureSampler, tex_coord1), blend);
+    var normal_map: vec4<f32> = mix(textureSample(normal_tex, textureSampler, tex_coord), textureSample(normal1_tex, textureSampler, tex_coord1), blend);
+    var specular_map: vec4<f32> = textureSample(specular_tex, textureSampler, tex_coord);
+
+    if (diffuse_map.a <= 0.5) {
+        discard;
+    }
+
+    diffuse_map = vec4<f32>(diffuse_map.rgb * bg.color.rgb, diffuse_map.a);
+
+    var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
+
+    return vec4<f32>(calculate_light(in.world_position, tbn, diffuse_map, normal_map, specular_map, 32.0), 1.0);
+}
@@ -0,0 +1,25 @@
+@group(0) @binding(10)
+var textureSampler: sampler;
+@group(0) @binding(11)
+var diffuse_tex: texture_2d<f32>;
+@group(0) @binding(12)
+var normal_tex: texture_2d<f32>;
+@group(0) @binding(13)
+var specular_tex: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var diffuse_map: vec4<f32> = textureSample(diffuse_tex, textureSampler, in.tex_coord);
+    var normal_map: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord);
+    var specular_map: vec4<f32> = textureSample(specular_tex, textureSampler, in.tex_coord);
+
+    var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
+    var color: vec3<f32> = calculate_light(in.world_position, tbn, diffuse_map, normal_map, specular_map, 128.0);
+
+    // crystals glow on edges
+    var eye_position: vec4<f32> = vec4<f32>(0.0, 0.8, 2.5, 1.0);
+    var view_dir: vec3<f32> = normalize(eye_position - in.world_position).xyz;
+    var rim: f32 = pow(1.0 - max(dot(normalize(in.normal.xyz), view_dir), 0.0), 3.0);
+
+    return vec4<f32>(color + diffuse_map.rgb * rim, 1.0);
+}
@@ -0,0 +1,12 @@
+// customization colors, bound at 20 by skin, hair and iris shaders
+struct customize_param {
+    skin_color: vec4<f32>,
+    hair_color: vec4<f32>,
+    highlight_color: vec4<f32>,
+    left_eye_color: vec4<f32>,
+    right_eye_color: vec4<f32>,
+    lip_color: vec4<f32>,
+    face_paint_color: vec4<f32>,
+    // x: flip face paint horizontally
+    face_paint_option: vec4<f32>,
+};
@@ -0,0 +1,24 @@
+@group(0) @binding(10)
+var textureSampler: sampler;
+@group(0) @binding(11)
+var normal_tex: texture_2d<f32>;
+@group(0) @binding(12)
+var diffuse_tex: texture_2d<f32>;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var normal_map: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord);
+    var diffuse_map: vec4<f32> = textureSample(diffuse_tex, textureSampler, in.tex_coord);
+
+    // missing textures are bound to empty texture
+    if (all(normal_map == vec4<f32>(0.0))) {
+        normal_map = vec4<f32>(0.5, 0.5, 1.0, 1.0);
+    }
+    if (all(diffuse_map == vec4<f32>(0.0))) {
+        diffuse_map = vec4<f32>(0.5, 0.5, 0.5, 1.0);
+    }
+
+    var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
+
+    return vec4<f32>(calculate_light(in.world_position, tbn, diffuse_map, normal_map, vec4<f32>(0.0, 0.0, 0.0, 1.0), 4.0), 1.0);
+}
@@ -4,16 +4,25 @@ var textureSampler: sampler;
 var normal_tex: texture_2d<f32>;
 @group(0) @binding(12)
 var diffuse_tex: texture_2d<f32>;
+@group(0) @binding(13)
+var mask_tex: texture_2d<f32>;
+
+@group(0) @binding(20)
+var<uniform> customize: customize_param;
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var normal_map: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord);
     var diffuse_map: vec4<f32> = textureSample(diffuse_tex, textureSampler, in.tex_coord);
+    var mask_map: vec4<f32> = textureSample(mask_tex, textureSampler, in.tex_coord);
 
     if (normal_map.a <= 0.5) {
         discard;
     }
 
+    // hair doesn't have diffuse texture, mask alpha channel marks highlights
+    diffuse_map = vec4<f32>(mix(customize.hair_color.rgb, customize.highlight_color.rgb, mask_map.a), 1.0);
+
     var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
 
     return vec4<f32>(calculate_light(in.world_position, tbn, diffuse_map, normal_map, vec4<f32>(0.0, 0.0, 0.0, 1.0), 4.0), 1.0);
@@ -4,16 +4,36 @@ var textureSampler: sampler;
 var normal_tex: texture_2d<f32>;
 @group(0) @binding(12)
 var diffuse_tex: texture_2d<f32>;
+@group(0) @binding(13)
+var mask_tex: texture_2d<f32>;
+@group(0) @binding(14)
+var decal_tex: texture_2d<f32>;
+
+@group(0) @binding(20)
+var<uniform> customize: customize_param;
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var normal_map: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord);
     var diffuse_map: vec4<f32> = textureSample(diffuse_tex, textureSampler, in.tex_coord);
+    var mask_map: vec4<f32> = textureSample(mask_tex, textureSampler, in.tex_coord);
 
     if (normal_map.b <= 0.5) {
         discard;
     }
 
+    // mask blue channel marks lips
+    var skin_color: vec3<f32> = diffuse_map.rgb * customize.skin_color.rgb;
+    diffuse_map = vec4<f32>(mix(skin_color, customize.lip_color.rgb, mask_map.b * customize.lip_color.a), diffuse_map.a);
+
+    // face paint decal is alpha mask colored by face paint color
+    var decal_coord: vec2<f32> = in.tex_coord;
+    if (customize.face_paint_option.x > 0.5) {
+        decal_coord.x = 1.0 - decal_coord.x;
+    }
+    var decal_map: vec4<f32> = textureSample(decal_tex, textureSampler, decal_coord);
+    diffuse_map = vec4<f32>(mix(diffuse_map.rgb, customize.face_paint_color.rgb, decal_map.a * customize.face_paint_color.a), diffuse_map.a);
+
     var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
 
     return vec4<f32>(calculate_light(in.world_position, tbn, diffuse_map, normal_map, vec4<f32>(0.0, 0.0, 0.0, 1.0), 4.0), 1.0);
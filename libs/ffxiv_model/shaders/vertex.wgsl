@@ -9,6 +9,7 @@ struct VertexOutput {
 	@location(4) tbn1: vec4<f32>,
 	@location(5) tbn2: vec4<f32>,
 	@location(6) tbn3: vec4<f32>,
+	@location(7) color: vec4<f32>,
 };
 
 struct transform {
@@ -62,6 +63,7 @@ fn vs_main(
 	out.tbn1 = vec4<f32>(tangent.y, normalized_bi_tangent.y, skinned_normal.y, 0.0);
 	out.tbn2 = vec4<f32>(tangent.z, normalized_bi_tangent.z, skinned_normal.z, 0.0);
 	out.tbn3 = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+	out.color = vec4<f32>(color) / 255.0;
 	out.position = transform.projection * transform.view * transform.model * skinned_position;
 
 	return out;
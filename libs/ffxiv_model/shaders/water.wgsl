@@ -0,0 +1,32 @@
+@group(0) @binding(10)
+var textureSampler: sampler;
+@group(0) @binding(11)
+var normal_tex: texture_2d<f32>;
+
+struct scene_param {
+    // x: time in seconds
+    time: vec4<f32>,
+};
+@group(0) @binding(21)
+var<uniform> scene: scene_param;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // two wave layers scrolling in different directions
+    var time: f32 = scene.time.x;
+    var wave0: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord + vec2<f32>(0.02, 0.01) * time);
+    var wave1: vec4<f32> = textureSample(normal_tex, textureSampler, in.tex_coord * 0.7 - vec2<f32>(0.01, 0.015) * time);
+    var normal_map: vec4<f32> = (wave0 + wave1) * 0.5;
+
+    // no normal map bound
+    if (all(normal_map == vec4<f32>(0.0))) {
+        normal_map = vec4<f32>(0.5, 0.5, 1.0, 1.0);
+    }
+
+    var diffuse_map: vec4<f32> = vec4<f32>(0.1, 0.3, 0.4, 1.0);
+    var specular_map: vec4<f32> = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+
+    var tbn: mat4x4<f32> = mat4x4<f32>(in.tbn0, in.tbn1, in.tbn2, in.tbn3);
+
+    return vec4<f32>(calculate_light(in.world_position, tbn, diffuse_map, normal_map, specular_map, 64.0), 1.0);
+}
@@ -1,23 +1,57 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use futures::{
     FutureExt,
     stream::{FuturesUnordered, TryStreamExt},
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 use eng::{
     ecs::{HierarchyExt, World},
     render::{RenderBundle, Renderer},
 };
+use ffxiv_parser::{EqpEntry, Pap};
+use havok_parser::{HavokAnimationBinding, HavokAnimationContainer, HavokReader};
 use sqpack::{Package, Result, SqPackReaderError};
 
 use crate::{
-    character_part::CharacterPart, constants::ModelPart, context::Context, customization::Customization, equipment::Equipment,
+    character_part::{BoneTable, CharacterPart},
+    constants::ModelPart,
+    context::Context,
+    customization::Customization,
+    equipment::Equipment,
     model_reader::ModelReader,
+    object::Object,
+    skeleton::Skeleton,
 };
 
-pub struct Character {}
+// attributes hidden by equipment, and the part having them. flags only affect parts other than the one setting them.
+const EQP_HIDDEN_ATTRIBUTES: [(EqpEntry, ModelPart, &str); 14] = [
+    (EqpEntry::BODY_HIDE_WAIST, ModelPart::Dwn, "atr_kod"),
+    (EqpEntry::BODY_HIDE_SMALL_GLOVES, ModelPart::Glv, "atr_arm"),
+    (EqpEntry::BODY_HIDE_MEDIUM_GLOVES, ModelPart::Glv, "atr_hij"),
+    (EqpEntry::BODY_HIDE_LARGE_GLOVES, ModelPart::Glv, "atr_ude"),
+    (EqpEntry::BODY_HIDE_GORGET, ModelPart::Met, "atr_nek"),
+    (EqpEntry::LEGS_HIDE_KNEE_PADS, ModelPart::Sho, "atr_lpd"),
+    (EqpEntry::LEGS_HIDE_SMALL_BOOTS, ModelPart::Sho, "atr_leg"),
+    (EqpEntry::LEGS_HIDE_MEDIUM_BOOTS, ModelPart::Sho, "atr_sne"),
+    (EqpEntry::HANDS_HIDE_ELBOW, ModelPart::Top, "atr_hij"),
+    (EqpEntry::HANDS_HIDE_FOREARM, ModelPart::Top, "atr_arm"),
+    (EqpEntry::FEET_HIDE_KNEE, ModelPart::Dwn, "atr_hiz"),
+    (EqpEntry::FEET_HIDE_CALF, ModelPart::Dwn, "atr_sne"),
+    (EqpEntry::FEET_HIDE_ANKLE, ModelPart::Dwn, "atr_leg"),
+    (EqpEntry::HEAD_HIDE_NECK, ModelPart::Top, "atr_nek"),
+];
+
+const MAIN_HAND_WEAPON_BONE: &str = "n_buki_r";
+const OFF_HAND_WEAPON_BONE: &str = "n_buki_l";
+
+pub struct Character {
+    skeleton: Skeleton,
+    bone_tables: Vec<BoneTable>,
+    animation: Option<HavokAnimationBinding>,
+    attachments: Vec<(String, Object)>,
+}
 
 impl Character {
     pub async fn load(
@@ -26,25 +60,34 @@ impl Character {
         context: &Context,
         customization: Customization,
         equipments: HashMap<ModelPart, Equipment>,
-    ) -> Result<()> {
+    ) -> Result<Self> {
         let entity = world.spawn().entity();
 
         let renderer = world.resource::<Renderer>().unwrap();
 
-        let bone_transforms = HashMap::new();
+        let equipment_parameter = Self::equipment_parameter(context, &equipments);
+        let hidden_attributes = Self::hidden_attributes(equipment_parameter);
+        let no_hidden_attributes = &HashSet::new();
+        let hidden_attributes_of = |part: ModelPart| hidden_attributes.get(&part).unwrap_or(no_hidden_attributes);
+
+        let body_parts = [ModelPart::Top, ModelPart::Glv, ModelPart::Dwn, ModelPart::Sho]
+            .into_iter()
+            .filter(|x| Self::shows_body(context, *x, equipments.get(x)))
+            .collect::<Vec<_>>();
+        let met_id = equipments.get(&ModelPart::Met).map(|x| x.model_id);
 
-        let read_futures = equipments
+        let parts_fut = equipments
             .into_iter()
-            .map(|(equipment_part, equipment)| ModelReader::read_equipment(renderer, package, &customization, equipment_part, equipment, context));
-        let parts_fut = read_futures
-            .map(|x| {
-                x.map(|data| {
-                    Ok::<Vec<RenderBundle>, SqPackReaderError>(CharacterPart::load_equipment_model(
+            .map(|(equipment_part, equipment)| {
+                let customization = &customization;
+                let hidden_attributes = hidden_attributes_of(equipment_part);
+                ModelReader::read_equipment(renderer, package, customization, equipment_part, equipment, context).map(move |data| {
+                    Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(CharacterPart::load_equipment_model(
                         renderer,
                         data?,
-                        &bone_transforms,
                         context,
-                        &customization,
+                        customization,
+                        hidden_attributes,
                     ))
                 })
             })
@@ -52,29 +95,213 @@ impl Character {
             .try_collect::<Vec<_>>();
 
         // chaining part model futures and equipment read futures requires boxed future, emits strange compile error https://github.com/rust-lang/rust/issues/64650
-        let face_part_fut = ModelReader::read_face(renderer, package, &customization, context)
-            .map(|x| Ok::<Vec<RenderBundle>, SqPackReaderError>(CharacterPart::load_model(renderer, x?, &bone_transforms, context, &customization)));
+        let face_part_fut = ModelReader::read_face(renderer, package, &customization, context).map(|x| {
+            Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(CharacterPart::load_model(
+                renderer,
+                x?,
+                context,
+                &customization,
+                no_hidden_attributes,
+            ))
+        });
+
+        // headgear hiding hair hides the whole hair model
+        let hair_part_fut = async {
+            if equipment_parameter.contains(EqpEntry::HEAD_HIDE_HAIR) {
+                return Ok(Vec::new());
+            }
+
+            let data = ModelReader::read_hair(renderer, package, &customization, context).await?;
+            Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(CharacterPart::load_model(
+                renderer,
+                data,
+                context,
+                &customization,
+                no_hidden_attributes,
+            ))
+        };
 
-        let hair_part_fut = ModelReader::read_hair(renderer, package, &customization, context)
-            .map(|x| Ok::<Vec<RenderBundle>, SqPackReaderError>(CharacterPart::load_model(renderer, x?, &bone_transforms, context, &customization)));
+        let tail_part_fut = async {
+            if customization.tail_id == 0 {
+                return Ok(Vec::new());
+            }
+
+            let data = ModelReader::read_tail(renderer, package, &customization, context).await?;
+            Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(CharacterPart::load_model(
+                renderer,
+                data,
+                context,
+                &customization,
+                no_hidden_attributes,
+            ))
+        };
+
+        let ear_part_fut = async {
+            if customization.ear_id == 0 {
+                return Ok(Vec::new());
+            }
 
-        let (parts, face_part, hair_part) = futures::future::try_join3(parts_fut, face_part_fut, hair_part_fut).await?;
+            let data = ModelReader::read_ear(renderer, package, &customization, context).await?;
+            Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(CharacterPart::load_model(
+                renderer,
+                data,
+                context,
+                &customization,
+                no_hidden_attributes,
+            ))
+        };
 
-        for part in parts {
+        // bare body is shown on empty or partially hiding body slots
+        let body_parts_fut = body_parts
+            .into_iter()
+            .map(|body_part| {
+                let customization = &customization;
+                let hidden_attributes = hidden_attributes_of(body_part);
+                ModelReader::read_body(renderer, package, customization, body_part, context).map(move |x| {
+                    Ok::<Vec<(RenderBundle, BoneTable)>, SqPackReaderError>(match x? {
+                        Some(data) => CharacterPart::load_body_model(renderer, data, context, customization, hidden_attributes),
+                        None => {
+                            log::warn!("No body model for {} of {}", body_part.as_path_str(), customization.body_id.id());
+
+                            Vec::new()
+                        }
+                    })
+                })
+            })
+            .collect::<FuturesUnordered<_>>()
+            .try_collect::<Vec<_>>();
+
+        let head_parts_fut = futures::future::try_join4(face_part_fut, hair_part_fut, tail_part_fut, ear_part_fut);
+
+        let skeleton_fut = Skeleton::new(
+            package,
+            context,
+            customization.body_id,
+            customization.face_id,
+            customization.hair_id,
+            met_id,
+        );
+
+        let (parts, body_parts, head_parts, skeleton) = futures::future::try_join4(parts_fut, body_parts_fut, head_parts_fut, skeleton_fut).await?;
+        let (face_part, hair_part, tail_part, ear_part) = head_parts;
+
+        let mut bone_tables = Vec::new();
+        for part in parts.into_iter().chain(body_parts) {
             let part_entity = world.spawn().entity();
             world.add_child(entity, part_entity);
 
-            for bundle in part {
+            for (bundle, bone_table) in part {
                 let part_child_entity = world.spawn_bundle(bundle);
                 world.add_child(part_entity, part_child_entity);
+
+                bone_tables.push(bone_table);
             }
         }
 
-        for bundle in face_part.into_iter().chain(hair_part.into_iter()) {
+        for (bundle, bone_table) in face_part.into_iter().chain(hair_part).chain(tail_part).chain(ear_part) {
             let part_entity = world.spawn_bundle(bundle);
             world.add_child(entity, part_entity);
+
+            bone_tables.push(bone_table);
+        }
+
+        Ok(Self {
+            skeleton,
+            bone_tables,
+            animation: None,
+            attachments: Vec::new(),
+        })
+    }
+
+    // equipment not enabling its own slot in eqp leaves the body visible
+    fn shows_body(context: &Context, part: ModelPart, equipment: Option<&Equipment>) -> bool {
+        const ENABLED_FLAGS: EqpEntry = EqpEntry::from_bits_truncate(
+            EqpEntry::BODY_ENABLED.bits() | EqpEntry::LEGS_ENABLED.bits() | EqpEntry::HANDS_ENABLED.bits() | EqpEntry::FEET_ENABLED.bits(),
+        );
+
+        equipment.is_none_or(|x| !context.get_equipment_parameter(x.model_id, part).intersects(ENABLED_FLAGS))
+    }
+
+    // each part only contributes its own section
+    fn equipment_parameter(context: &Context, equipments: &HashMap<ModelPart, Equipment>) -> EqpEntry {
+        equipments.iter().fold(EqpEntry::empty(), |x, (&part, equipment)| {
+            x | context.get_equipment_parameter(equipment.model_id, part)
+        })
+    }
+
+    fn hidden_attributes(equipment_parameter: EqpEntry) -> HashMap<ModelPart, HashSet<&'static str>> {
+        let mut result = HashMap::<ModelPart, HashSet<&'static str>>::new();
+        for &(_, part, attribute) in EQP_HIDDEN_ATTRIBUTES.iter().filter(|(flag, _, _)| equipment_parameter.contains(*flag)) {
+            result.entry(part).or_default().insert(attribute);
         }
 
+        result
+    }
+
+    pub async fn play(&mut self, package: &dyn Package, pap_path: &str, animation_index: usize) -> Result<()> {
+        let pap = Pap::new(package, pap_path).await?;
+
+        self.animation = Self::read_bindings(&pap, pap_path).into_iter().nth(animation_index);
+
+        Ok(())
+    }
+
+    // plays animation by name in pap, e.g. "idle". does nothing if there's no such animation.
+    pub async fn play_by_name(&mut self, package: &dyn Package, pap_path: &str, animation_name: &str) -> Result<()> {
+        let pap = Pap::new(package, pap_path).await?;
+
+        let Some(animation) = pap.find_animation(animation_name) else {
+            return Ok(());
+        };
+
+        self.animation = Self::read_bindings(&pap, pap_path).into_iter().nth(animation.havok_index as usize);
+
         Ok(())
     }
+
+    // animations we can't decode are not played
+    fn read_bindings(pap: &Pap, pap_path: &str) -> Vec<HavokAnimationBinding> {
+        let container = HavokReader::read(pap.hkx_data()).and_then(|x| HavokAnimationContainer::new(x.find_object_by_type("hkaAnimationContainer")));
+
+        match container {
+            Ok(x) => x.bindings,
+            Err(err) => {
+                log::warn!("Can't read animations in {pap_path}: {err:?}");
+
+                Vec::new()
+            }
+        }
+    }
+
+    // attached object follows the bone
+    pub fn attach(&mut self, object: Object, bone_name: &str) {
+        if let Some(matrix) = self.skeleton.bone_matrix(self.skeleton.reference_pose(), bone_name) {
+            object.update(matrix);
+        }
+
+        self.attachments.push((bone_name.into(), object));
+    }
+
+    pub fn attach_weapon(&mut self, weapon: Object, off_hand: bool) {
+        self.attach(weapon, if off_hand { OFF_HAND_WEAPON_BONE } else { MAIN_HAND_WEAPON_BONE });
+    }
+
+    // time in milliseconds
+    pub fn update(&self, time: f32) {
+        if let Some(animation) = &self.animation {
+            let duration = animation.animation.duration() * 1000.;
+            let local_pose = self.skeleton.sample_pose(animation, time % duration);
+            let skinning_matrices = self.skeleton.skinning_matrices(&local_pose);
+
+            for bone_table in &self.bone_tables {
+                bone_table.update(&skinning_matrices);
+            }
+
+            for (bone_name, object) in &self.attachments {
+                if let Some(matrix) = self.skeleton.bone_matrix(&local_pose, bone_name) {
+                    object.update(matrix);
+                }
+            }
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use alloc::{string::String, sync::Arc, vec::Vec};
+use alloc::{borrow::ToOwned, string::String, sync::Arc, vec::Vec};
 use core::ops::Range;
 
 use glam::Mat4;
@@ -11,7 +11,39 @@ use ffxiv_parser::{BufferItemChunk, BufferItemType, BufferItemUsage, Mdl, MdlMes
 use crate::context::Context;
 use crate::customization::Customization;
 use crate::material::create_material;
-use crate::model_reader::{EquipmentModelData, ModelData};
+use crate::model_reader::{BodyModelData, EquipmentModelData, ModelData, ObjectModelData};
+
+pub struct BoneTable {
+    bone_names: Vec<String>,
+    deform_matrices: Vec<Mat4>,
+    buffer: Arc<Buffer>,
+}
+
+impl BoneTable {
+    const BUFFER_SIZE: usize = 64 * 3 * 4 * core::mem::size_of::<f32>(); // to match uniform size with shader. do we really require this?
+
+    pub fn update(&self, skinning_matrices: &HashMap<&str, Mat4>) {
+        let mut bone_transform_data = Vec::with_capacity(Self::BUFFER_SIZE);
+        for (bone_name, deform_matrix) in self.bone_names.iter().zip(self.deform_matrices.iter()) {
+            let skinning_matrix = skinning_matrices.get(bone_name.as_str()).cloned().unwrap_or(Mat4::IDENTITY);
+
+            // shader takes first three rows
+            let matrix = (skinning_matrix * *deform_matrix).transpose();
+            bone_transform_data.extend(matrix.to_cols_array()[..12].as_bytes());
+        }
+
+        bone_transform_data.resize(Self::BUFFER_SIZE, 0);
+
+        self.buffer.write(0, &bone_transform_data);
+    }
+
+    // moves every bone by same matrix, used on rigid models attached to other skeleton
+    pub fn update_rigid(&self, matrix: Mat4) {
+        let skinning_matrices = self.bone_names.iter().map(|x| (x.as_str(), matrix)).collect::<HashMap<_, _>>();
+
+        self.update(&skinning_matrices);
+    }
+}
 
 pub struct CharacterPart {}
 
@@ -19,79 +51,127 @@ impl CharacterPart {
     pub fn load_model(
         renderer: &Renderer,
         model_data: ModelData,
-        bone_transforms: &HashMap<String, Mat4>,
         context: &Context,
         customization: &Customization,
-    ) -> Vec<RenderBundle> {
-        let mdl = model_data.mdl;
-
-        let visibility_mask = 0;
-        let hidden_attributes = HashSet::new();
-        let lod = 0;
-
-        mdl.meshes(lod)
-            .zip(mdl.buffer_items(lod))
-            .zip(model_data.mtrls)
-            .map(|((mesh_data, buffer_item), (mtrl, texs))| {
-                let mesh = Self::load_mesh(renderer, &mesh_data, buffer_item);
-                let mesh_parts = Self::get_mesh_parts(&mdl, &mesh_data, visibility_mask, &hidden_attributes);
-                let bone_transform = Self::load_bone_transform(renderer, &mdl, &mesh_data, bone_transforms);
-
-                let material = create_material(renderer, context, &mtrl, &texs, bone_transform, customization, 0);
-
-                RenderBundle {
-                    mesh,
-                    material,
-                    ranges: Some(mesh_parts),
-                    transform: Transform::new(),
-                }
-            })
-            .collect::<Vec<_>>()
+        hidden_attributes: &HashSet<&str>,
+    ) -> Vec<(RenderBundle, BoneTable)> {
+        Self::load(
+            renderer,
+            model_data,
+            context,
+            Some(customization),
+            0,
+            hidden_attributes,
+            &HashMap::new(),
+            0,
+        )
     }
 
     pub fn load_equipment_model(
         renderer: &Renderer,
         equipment_model_data: EquipmentModelData,
-        _bone_transforms: &HashMap<String, Mat4>,
         context: &Context,
         customization: &Customization,
-    ) -> Vec<RenderBundle> {
+        hidden_attributes: &HashSet<&str>,
+    ) -> Vec<(RenderBundle, BoneTable)> {
         log::debug!(
             "original {:?} deformed {:?}",
-            equipment_model_data.original_body_id as u16,
-            equipment_model_data.deformed_body_id as u16
+            equipment_model_data.original_body_id.id(),
+            equipment_model_data.deformed_body_id.id()
         );
         let prebone_deformer = context.get_body_deform_matrices(equipment_model_data.original_body_id, equipment_model_data.deformed_body_id);
-        let mdl = equipment_model_data.model_data.mdl;
 
-        let visibility_mask = 0;
-        let hidden_attributes = HashSet::new();
+        Self::load(
+            renderer,
+            equipment_model_data.model_data,
+            context,
+            Some(customization),
+            equipment_model_data.visibility_mask,
+            hidden_attributes,
+            &prebone_deformer,
+            equipment_model_data.stain_id,
+        )
+    }
+
+    // shared body models are deformed to the character's body
+    pub fn load_body_model(
+        renderer: &Renderer,
+        body_model_data: BodyModelData,
+        context: &Context,
+        customization: &Customization,
+        hidden_attributes: &HashSet<&str>,
+    ) -> Vec<(RenderBundle, BoneTable)> {
+        let prebone_deformer = context.get_body_deform_matrices(body_model_data.original_body_id, body_model_data.deformed_body_id);
+
+        Self::load(
+            renderer,
+            body_model_data.model_data,
+            context,
+            Some(customization),
+            0,
+            hidden_attributes,
+            &prebone_deformer,
+            0,
+        )
+    }
+
+    pub fn load_object_model(renderer: &Renderer, object_model_data: ObjectModelData, context: &Context) -> Vec<(RenderBundle, BoneTable)> {
+        Self::load(
+            renderer,
+            object_model_data.model_data,
+            context,
+            None,
+            object_model_data.visibility_mask,
+            &HashSet::new(),
+            &HashMap::new(),
+            object_model_data.stain_id,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn load(
+        renderer: &Renderer,
+        model_data: ModelData,
+        context: &Context,
+        customization: Option<&Customization>,
+        visibility_mask: usize,
+        hidden_attributes: &HashSet<&str>,
+        deform_matrices: &HashMap<String, Mat4>,
+        stain_id: u8,
+    ) -> Vec<(RenderBundle, BoneTable)> {
+        let mdl = model_data.mdl;
+        let decal = model_data.decal;
         let lod = 0;
 
         mdl.meshes(lod)
             .zip(mdl.buffer_items(lod))
-            .zip(equipment_model_data.model_data.mtrls)
+            .zip(model_data.mtrls)
+            // occlusion meshes only darken other parts in game, we don't draw them
+            .filter(|(_, (mtrl, _))| mtrl.shader_name() != "characterocclusion.shpk")
             .map(|((mesh_data, buffer_item), (mtrl, texs))| {
                 let mesh = Self::load_mesh(renderer, &mesh_data, buffer_item);
-                let mesh_parts = Self::get_mesh_parts(&mdl, &mesh_data, visibility_mask, &hidden_attributes);
-                let bone_transform = Self::load_bone_transform(renderer, &mdl, &mesh_data, &prebone_deformer);
+                let mesh_parts = Self::get_mesh_parts(&mdl, &mesh_data, visibility_mask, hidden_attributes);
+                let bone_table = Self::load_bone_table(renderer, &mdl, &mesh_data, deform_matrices);
 
                 let material = create_material(
                     renderer,
                     context,
                     &mtrl,
                     &texs,
-                    bone_transform,
+                    bone_table.buffer.clone(),
                     customization,
-                    equipment_model_data.stain_id,
+                    decal.as_ref(),
+                    stain_id,
                 );
 
-                RenderBundle {
+                let bundle = RenderBundle {
                     mesh,
                     material,
                     ranges: Some(mesh_parts),
                     transform: Transform::new(),
-                }
+                };
+
+                (bundle, bone_table)
             })
             .collect::<Vec<_>>()
     }
@@ -148,24 +228,25 @@ impl CharacterPart {
             .collect::<Vec<_>>()
     }
 
-    fn load_bone_transform(renderer: &Renderer, mdl: &Mdl, mesh_data: &MdlMesh<'_>, bone_transforms: &HashMap<String, Mat4>) -> Arc<Buffer> {
-        let bone_names = mdl.bone_names(mesh_data.mesh_info.bone_index);
-        let mut bone_transform_data = Vec::with_capacity(64 * 3 * 4 * core::mem::size_of::<f32>());
-        for bone_name in bone_names {
-            if let Some(x) = bone_transforms.get(bone_name) {
-                bone_transform_data.extend(x.to_cols_array()[..12].as_bytes());
-            } else {
-                let identity = [1.0f32, 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0.];
-                bone_transform_data.extend(identity.as_bytes());
-            }
-        }
+    fn load_bone_table(renderer: &Renderer, mdl: &Mdl, mesh_data: &MdlMesh<'_>, deform_matrices: &HashMap<String, Mat4>) -> BoneTable {
+        let bone_names = mdl.bone_names(mesh_data.mesh_info.bone_index).map(|x| x.to_owned()).collect::<Vec<_>>();
+
+        // prebone deformer matrices are stored transposed
+        let deform_matrices = bone_names
+            .iter()
+            .map(|x| deform_matrices.get(x).map(|x| x.transpose()).unwrap_or(Mat4::IDENTITY))
+            .collect::<Vec<_>>();
 
-        bone_transform_data.resize(64 * 3 * 4 * core::mem::size_of::<f32>(), 0); // to match uniform size with shader. do we really require this?
+        let buffer = Arc::new(renderer.buffer_pool.alloc(BoneTable::BUFFER_SIZE as u64));
 
-        let bone_transform = Arc::new(renderer.buffer_pool.alloc(bone_transform_data.len() as u64));
-        bone_transform.write(0, &bone_transform_data);
+        let bone_table = BoneTable {
+            bone_names,
+            deform_matrices,
+            buffer,
+        };
+        bone_table.update(&HashMap::new());
 
-        bone_transform
+        bone_table
     }
 
     fn convert_buffer_type(item_type: BufferItemType) -> VertexItemType {
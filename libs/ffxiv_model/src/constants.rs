@@ -1,5 +1,3 @@
-use enum_iterator::Sequence;
-
 #[derive(Eq, PartialEq, Hash, Copy, Clone)]
 #[repr(u8)]
 pub enum ModelPart {
@@ -34,48 +32,47 @@ impl ModelPart {
             ModelPart::Fac => "fac",
         }
     }
-}
 
-#[derive(Clone, Copy, Sequence, Eq, PartialEq, Hash)]
-#[repr(u16)]
-pub enum BodyId {
-    MidlanderMale = 101,
-    MidlanderFemale = 201,
-    HighlanderMale = 301,
-    HighlanderFemale = 401,
-    ElezenMale = 501,
-    ElezenFemale = 601,
-    MiqoteMale = 701,
-    MiqoteFemale = 801,
-    RoegadynMale = 901,
-    RoegadynFemale = 1001,
-    LalafellMale = 1101,
-    LalafellFemale = 1201,
-    AuRaMale = 1301,
-    AuRaFemale = 1401,
-    HrothgarMale = 1501,
-    // HrothgarFemale = 1601,
-    // VieraMale = 1701,
-    VieraFemale = 1801,
+    pub fn is_accessory(&self) -> bool {
+        matches!(self, ModelPart::Ear | ModelPart::Nek | ModelPart::Wrs | ModelPart::Rir | ModelPart::Ril)
+    }
 
-    ChildHyurMale = 104,
-    ChildHyurFemale = 204,
-    ChildElezenMale = 504,
-    ChildElezenFemale = 604,
-    ChildMiqoteMale = 704,
-    ChildMiqoteFemale = 804,
-    ChildAuRaMale = 1304,
-    ChildAuRaFemale = 1404,
-    Unk9104 = 9104,
-    Unk9204 = 9204,
+    // index of part in equipment or accessory imc. hair and face are not part of them.
+    pub fn imc_part_index(&self) -> Option<usize> {
+        match self {
+            ModelPart::Met | ModelPart::Ear => Some(0),
+            ModelPart::Top | ModelPart::Nek => Some(1),
+            ModelPart::Glv | ModelPart::Wrs => Some(2),
+            ModelPart::Dwn | ModelPart::Rir => Some(3),
+            ModelPart::Sho | ModelPart::Ril => Some(4),
+            ModelPart::Hir | ModelPart::Fac => None,
+        }
+    }
 }
 
+// human model id, as in chara/human/c{id:04}. hundreds are race and gender, ones are 1 for adult and 4 for child.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct BodyId(u16);
+
+#[allow(non_upper_case_globals)]
 impl BodyId {
+    // root of deformer tree, equipment models fall back to it
+    pub const MidlanderMale: BodyId = BodyId(101);
+    pub const AuRaFemale: BodyId = BodyId(1401);
+
+    pub const fn new(id: u16) -> Self {
+        Self(id)
+    }
+
+    pub fn id(self) -> u16 {
+        self.0
+    }
+
     pub fn is_male(self) -> bool {
-        (self as u16 / 100) == 1
+        (self.0 / 100) % 2 == 1
     }
 
     pub fn is_child(self) -> bool {
-        (self as u16 % 100) == 4
+        (self.0 % 100) == 4
     }
 }
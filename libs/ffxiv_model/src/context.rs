@@ -1,17 +1,17 @@
-use alloc::{format, string::String, sync::Arc};
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+use core::mem::size_of;
 
-use futures::{
-    FutureExt,
-    stream::{FuturesUnordered, TryStreamExt},
-};
+use futures::{FutureExt, StreamExt, stream::FuturesUnordered};
 use glam::Mat4;
 use hashbrown::HashMap;
+use zerocopy::AsBytes;
 
-use eng::render::{Renderer, Texture, TextureFormat};
-use ffxiv_parser::{Eqdp, Pbd, Stm};
-use sqpack::{Package, Result};
+use eng::render::{Buffer, Renderer, Texture, TextureFormat};
+use ffxiv_parser::{Cmp, Eqdp, Eqp, EqpEntry, Est, Ex, Pbd, Stm};
+use sqpack::{Package, Result, SqPackReaderError};
 
 use crate::constants::{BodyId, ModelPart};
+use crate::customization::Customization;
 use crate::shader_holder::ShaderHolder;
 use crate::texture_cache::TextureCache;
 
@@ -20,79 +20,213 @@ pub struct Context {
     pub(crate) texture_cache: TextureCache,
     pub(crate) empty_texture: Arc<Texture>,
     pub(crate) staining_template: Stm,
+    pub(crate) scene_buffer: Arc<Buffer>,
     equipment_deformer_parameters: HashMap<BodyId, Eqdp>,
+    accessory_deformer_parameters: HashMap<BodyId, Eqdp>,
+    equipment_parameter: Eqp,
     prebone_deformer: Pbd,
+    chara_make_parameter: Cmp,
+    face_skeleton_template: Est,
+    hair_skeleton_template: Est,
+    head_skeleton_template: Est,
+    stain_colors: HashMap<u8, [f32; 4]>,
 }
 
 impl Context {
     pub async fn new(renderer: &Renderer, package: &dyn Package) -> Result<Self> {
         let empty_texture = Self::create_empty_texture(renderer).await;
-        let equipment_deformer_parameters = Self::create_equipment_deformer_parameters(package).await?;
         let prebone_deformer = Pbd::new(package).await?;
+        let body_ids = prebone_deformer.body_ids().into_iter().map(BodyId::new).collect::<Vec<_>>();
+        let equipment_deformer_parameters = Self::create_deformer_parameters(package, "equipmentdeformerparameter", &body_ids).await?;
+        let accessory_deformer_parameters = Self::create_deformer_parameters(package, "accessorydeformerparameter", &body_ids).await?;
+        let equipment_parameter = Eqp::new(package).await?;
         let staining_template = Stm::new(package).await?;
+        let chara_make_parameter = Cmp::new(package).await?;
+        let stain_colors = Self::read_stain_colors(package).await?;
+        let face_skeleton_template = Est::new(package, Est::FACE_PATH).await?;
+        let hair_skeleton_template = Est::new(package, Est::HAIR_PATH).await?;
+        let head_skeleton_template = Est::new(package, Est::HEAD_PATH).await?;
+
+        let scene_buffer = Arc::new(renderer.buffer_pool.alloc(size_of::<[f32; 4]>() as u64));
+        scene_buffer.write(0, [0f32; 4].as_bytes());
 
         Ok(Self {
             shader_holder: ShaderHolder::new(renderer),
             texture_cache: TextureCache::new(),
             empty_texture,
             staining_template,
+            scene_buffer,
             equipment_deformer_parameters,
+            accessory_deformer_parameters,
+            equipment_parameter,
             prebone_deformer,
+            chara_make_parameter,
+            face_skeleton_template,
+            hair_skeleton_template,
+            head_skeleton_template,
+            stain_colors,
         })
     }
 
+    // textures in use are kept alive by their materials
+    pub fn clear_texture_cache(&self) {
+        self.texture_cache.clear();
+    }
+
+    // time in milliseconds like Character::update, drives animated materials. shaders take seconds.
+    pub fn update_time(&self, time: f32) {
+        self.scene_buffer.write(0, [time / 1000., 0., 0., 0.].as_bytes());
+    }
+
     pub fn get_body_deform_matrices(&self, from_id: BodyId, to_id: BodyId) -> HashMap<String, Mat4> {
-        self.prebone_deformer.get_deform_matrices(from_id as u16, to_id as u16)
+        self.prebone_deformer.get_deform_matrices(from_id.id(), to_id.id())
     }
 
-    pub fn get_deformed_body_id(&self, body_id: BodyId, model_id: u16, model_part: ModelPart) -> BodyId {
-        if body_id == BodyId::MidlanderMale {
-            return BodyId::MidlanderMale;
-        }
+    // partial skeleton id of face, hair or helmet. None if the part has no skeleton.
+    pub fn get_extra_skeleton_id(&self, body_id: BodyId, model_part: ModelPart, set_id: u16) -> Option<u16> {
+        let template = match model_part {
+            ModelPart::Fac => &self.face_skeleton_template,
+            ModelPart::Hir => &self.hair_skeleton_template,
+            ModelPart::Met => &self.head_skeleton_template,
+            _ => return None,
+        };
 
-        let eqdp = self.equipment_deformer_parameters.get(&body_id).unwrap();
-        if eqdp.has_model(model_id, model_part as u8) {
-            body_id
-        } else {
-            if body_id == BodyId::MidlanderFemale {
-                return BodyId::MidlanderMale;
-            }
+        template.get(set_id, body_id.id())
+    }
 
-            let search_id = if body_id == BodyId::LalafellFemale {
-                BodyId::LalafellMale
-            } else if body_id.is_child() {
-                BodyId::ChildHyurMale
-            } else if body_id == BodyId::HrothgarMale {
-                BodyId::RoegadynMale
-            } else if body_id.is_male() {
-                BodyId::MidlanderMale
-            } else {
-                BodyId::MidlanderFemale
-            };
+    // skin, hair, highlight, left eye, right eye, lip, face paint. white if colors are not set.
+    pub fn get_customization_colors(&self, customization: &Customization) -> [[f32; 4]; 7] {
+        const LIST_SIZE: usize = 256;
+        const EYE_OFFSET: usize = 0;
+        const HIGHLIGHT_OFFSET: usize = 256;
+        const DARK_LIP_OFFSET: usize = 512;
+        const DARK_FACE_PAINT_OFFSET: usize = 640;
+        const LIGHT_LIP_OFFSET: usize = 1024;
+        const LIGHT_FACE_PAINT_OFFSET: usize = 1152;
+
+        let Some(colors) = &customization.colors else {
+            return [[1.; 4]; 7];
+        };
+        let palette = self.chara_make_parameter.colors();
+
+        // each tribe and gender has 5 lists, skin and hair being last two
+        let gender = if customization.body_id.is_male() { 0 } else { 1 };
+        let tribe_list = (colors.tribe_id as usize * 2 + gender) * 5;
 
-            let eqdp = self.equipment_deformer_parameters.get(&search_id).unwrap();
-            if eqdp.has_model(model_id, model_part as u8) {
-                return search_id;
+        // lip and face paint colors above 128 are light colors
+        let dark_or_light = |index: u8, dark_offset: usize, light_offset: usize| {
+            if index < 128 {
+                dark_offset + index as usize
+            } else {
+                light_offset + index as usize - 128
             }
-            BodyId::MidlanderMale
+        };
+
+        [
+            (tribe_list + 3) * LIST_SIZE + colors.skin as usize,
+            (tribe_list + 4) * LIST_SIZE + colors.hair as usize,
+            HIGHLIGHT_OFFSET + colors.highlight as usize,
+            EYE_OFFSET + colors.left_eye as usize,
+            EYE_OFFSET + colors.right_eye as usize,
+            dark_or_light(colors.lip, DARK_LIP_OFFSET, LIGHT_LIP_OFFSET),
+            dark_or_light(colors.face_paint, DARK_FACE_PAINT_OFFSET, LIGHT_FACE_PAINT_OFFSET),
+        ]
+        .map(|index| palette.get(index).copied().unwrap_or([255, 255, 255, 255]).map(|x| x as f32 / 255.))
+    }
+
+    // each model part only uses its own section of eqp entry
+    pub fn get_equipment_parameter(&self, model_id: u16, model_part: ModelPart) -> EqpEntry {
+        let section_mask = match model_part {
+            ModelPart::Top => 0xffff,
+            ModelPart::Dwn => 0xff << 16,
+            ModelPart::Glv => 0xff << 24,
+            ModelPart::Sho => 0xff << 32,
+            ModelPart::Met => 0xff_ffff << 40,
+            _ => 0,
+        };
+
+        EqpEntry::from_bits_truncate(self.equipment_parameter.get(model_id).bits() & section_mask)
+    }
+
+    pub fn get_stain_color(&self, stain_id: u8) -> [f32; 4] {
+        self.stain_colors.get(&stain_id).copied().unwrap_or([1.; 4])
+    }
+
+    // walks up deformer tree until a body having the model is found
+    pub fn get_deformed_body_id(&self, body_id: BodyId, model_id: u16, model_part: ModelPart) -> BodyId {
+        let deformer_parameters = if model_part.is_accessory() {
+            &self.accessory_deformer_parameters
+        } else {
+            &self.equipment_deformer_parameters
+        };
+        let Some(slot) = model_part.imc_part_index() else {
+            return body_id;
+        };
+        let slot = slot as u8;
+
+        self.get_deformer_chain(body_id)
+            .into_iter()
+            .find(|x| deformer_parameters.get(x).is_some_and(|eqdp| eqdp.has_model(model_id, slot)))
+            .unwrap_or(BodyId::MidlanderMale)
+    }
+
+    // body itself followed by its ancestors in deformer tree
+    pub fn get_deformer_chain(&self, body_id: BodyId) -> Vec<BodyId> {
+        let mut result = Vec::new();
+
+        let mut search_id = Some(body_id);
+        while let Some(current) = search_id {
+            result.push(current);
+
+            search_id = self.prebone_deformer.parent_body_id(current.id()).map(BodyId::new);
         }
+
+        result
     }
 
     async fn create_empty_texture(renderer: &Renderer) -> Arc<Texture> {
         Arc::new(Texture::with_texels(renderer, 1, 1, &[0, 0, 0, 0], TextureFormat::Rgba8Unorm))
     }
 
-    async fn create_equipment_deformer_parameters(package: &dyn Package) -> Result<HashMap<BodyId, Eqdp>> {
-        enum_iterator::all::<BodyId>()
-            .map(|body_id| {
-                Eqdp::new(
-                    package,
-                    format!("chara/xls/charadb/equipmentdeformerparameter/c{:04}.eqdp", body_id as u16),
-                )
-                .map(move |eqdp| Ok((body_id, eqdp?)))
+    // not every body in deformer tree has its own eqdp
+    async fn create_deformer_parameters(package: &dyn Package, kind: &str, body_ids: &[BodyId]) -> Result<HashMap<BodyId, Eqdp>> {
+        let mut eqdps = body_ids
+            .iter()
+            .map(|&body_id| Eqdp::new(package, format!("chara/xls/charadb/{kind}/c{:04}.eqdp", body_id.id())).map(move |eqdp| (body_id, eqdp)))
+            .collect::<FuturesUnordered<_>>();
+
+        let mut result = HashMap::new();
+        while let Some((body_id, eqdp)) = eqdps.next().await {
+            match eqdp {
+                Ok(x) => {
+                    result.insert(body_id, x);
+                }
+                Err(SqPackReaderError::NoSuchFile) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(result)
+    }
+
+    // Stain rows start with 0xRRGGBB color
+    async fn read_stain_colors(package: &dyn Package) -> Result<HashMap<u8, [f32; 4]>> {
+        let stain = Ex::new(package, "stain").await?;
+        let Some(&language) = stain.languages().first() else {
+            return Ok(HashMap::new());
+        };
+
+        Ok(stain
+            .all(language)
+            .into_iter()
+            .flatten()
+            .filter(|&(id, _)| id != 0)
+            .map(|(id, row)| {
+                let color = row.uint32(0);
+                let [_, r, g, b] = color.to_be_bytes();
+
+                (id as u8, [r as f32 / 255., g as f32 / 255., b as f32 / 255., 1.])
             })
-            .collect::<FuturesUnordered<_>>()
-            .try_collect::<HashMap<_, _>>()
-            .await
+            .collect())
     }
 }
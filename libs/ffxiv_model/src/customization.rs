@@ -1,5 +1,18 @@
 use crate::constants::BodyId;
 
+// palette indices in human.cmp. tribe selects skin and hair palette.
+#[derive(Clone, Copy, Default)]
+pub struct CustomizationColors {
+    pub tribe_id: u8,
+    pub skin: u8,
+    pub hair: u8,
+    pub highlight: u8,
+    pub left_eye: u8,
+    pub right_eye: u8,
+    pub lip: u8,
+    pub face_paint: u8,
+}
+
 pub struct Customization {
     pub(crate) body_id: BodyId,
     pub(crate) body_type: u8,
@@ -7,6 +20,11 @@ pub struct Customization {
     pub(crate) face_id: u8,
     pub(crate) hair_id: u8,
     pub(crate) hair_variant_id: u8,
+    pub(crate) tail_id: u8,
+    pub(crate) ear_id: u8,
+    pub(crate) colors: Option<CustomizationColors>,
+    pub(crate) face_paint_id: u8,
+    pub(crate) face_paint_flip: bool,
 }
 
 impl Customization {
@@ -18,6 +36,40 @@ impl Customization {
             face_id,
             hair_id,
             hair_variant_id,
+            tail_id: 0,
+            ear_id: 0,
+            colors: None,
+            face_paint_id: 0,
+            face_paint_flip: false,
         }
     }
+
+    // miqo'te, au ra and hrothgar tail
+    pub fn with_tail(mut self, tail_id: u8) -> Self {
+        self.tail_id = tail_id;
+
+        self
+    }
+
+    // viera and hrothgar ear
+    pub fn with_ear(mut self, ear_id: u8) -> Self {
+        self.ear_id = ear_id;
+
+        self
+    }
+
+    // without colors, customized parts are left untinted
+    pub fn with_colors(mut self, colors: CustomizationColors) -> Self {
+        self.colors = Some(colors);
+
+        self
+    }
+
+    // face_paint_id 0 is no face paint. flip mirrors decal horizontally.
+    pub fn with_face_paint(mut self, face_paint_id: u8, flip: bool) -> Self {
+        self.face_paint_id = face_paint_id;
+        self.face_paint_flip = flip;
+
+        self
+    }
 }
@@ -9,11 +9,16 @@ mod customization;
 mod equipment;
 mod material;
 mod model_reader;
+mod object;
+mod object_model;
 mod shader_holder;
+mod skeleton;
 mod texture_cache;
 
 pub use character::Character;
 pub use constants::{BodyId, ModelPart};
 pub use context::Context;
-pub use customization::Customization;
+pub use customization::{Customization, CustomizationColors};
 pub use equipment::Equipment;
+pub use object::Object;
+pub use object_model::{ObjectModel, ObjectModelType};
@@ -1,11 +1,17 @@
+mod bg_material;
 mod character_material;
+mod crystal_material;
+mod fallback_material;
 mod hair_material;
 mod iris_material;
 mod skin_material;
+mod tattoo_material;
+mod water_material;
 
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 use hashbrown::HashMap;
+use zerocopy::AsBytes;
 
 use eng::render::{Buffer, Material, Renderer, Resource, Texture};
 use ffxiv_parser::{Mtrl, MtrlParameterType};
@@ -13,13 +19,15 @@ use ffxiv_parser::{Mtrl, MtrlParameterType};
 use crate::context::Context;
 use crate::customization::Customization;
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_material(
     renderer: &Renderer,
     context: &Context,
     mtrl: &Mtrl,
     textures: &[Arc<Texture>],
     bone_transform: Arc<Buffer>,
-    #[allow(unused_variables)] customization: &Customization,
+    customization: Option<&Customization>,
+    decal: Option<&Arc<Texture>>,
     stain_id: u8,
 ) -> Material {
     // we can't move textures because of https://github.com/rust-lang/rust/issues/63033
@@ -27,32 +35,85 @@ pub fn create_material(
     resources.insert("bone_transform", bone_transform);
 
     match mtrl.shader_name() {
-        "character.shpk" | "characterglass.shpk" => character_material::CharacterMaterial::create(renderer, context, mtrl, stain_id, resources),
-        "hair.shpk" => hair_material::HairMaterial::create(renderer, context, resources),
-        "iris.shpk" => iris_material::IrisMaterial::create(renderer, context, resources),
-        "skin.shpk" => skin_material::SkinMaterial::create(renderer, context, resources),
-        _ => panic!(),
+        "character.shpk" | "characterglass.shpk" | "characterlegacy.shpk" | "characterstockings.shpk" | "characterreflection.shpk" => {
+            // color table is required to get diffuse color
+            if mtrl.color_table().is_empty() {
+                fallback_material::FallbackMaterial::create(renderer, context, resources)
+            } else {
+                character_material::CharacterMaterial::create(renderer, context, mtrl, stain_id, resources)
+            }
+        }
+        "hair.shpk" => {
+            resources.insert("customize", create_customize_buffer(renderer, context, customization));
+            hair_material::HairMaterial::create(renderer, context, resources)
+        }
+        "iris.shpk" => {
+            resources.insert("customize", create_customize_buffer(renderer, context, customization));
+            iris_material::IrisMaterial::create(renderer, context, resources)
+        }
+        "skin.shpk" => {
+            resources.insert("customize", create_customize_buffer(renderer, context, customization));
+            resources.insert("decal_tex", decal.cloned().unwrap_or_else(|| context.empty_texture.clone()));
+            skin_material::SkinMaterial::create(renderer, context, resources)
+        }
+        // bg, water and crystal shaders are used by bg/ models, which nothing loads yet. chara models don't use them.
+        "bg.shpk" => bg_material::BgMaterial::create(renderer, context, mtrl, bg_material::BgMaterialType::Bg, stain_id, resources),
+        "bgcolorchange.shpk" => {
+            bg_material::BgMaterial::create(renderer, context, mtrl, bg_material::BgMaterialType::ColorChange, stain_id, resources)
+        }
+        "bguvscroll.shpk" => bg_material::BgMaterial::create(renderer, context, mtrl, bg_material::BgMaterialType::UvScroll, stain_id, resources),
+        "water.shpk" => water_material::WaterMaterial::create(renderer, context, resources),
+        "crystal.shpk" => crystal_material::CrystalMaterial::create(renderer, context, resources),
+        "charactertattoo.shpk" => tattoo_material::TattooMaterial::create(renderer, context, resources),
+        shader_name => {
+            log::warn!("Unsupported shader {shader_name}, using fallback material");
+
+            fallback_material::FallbackMaterial::create(renderer, context, resources)
+        }
     }
 }
 
+// customization colors and face paint options uniform, white if there's no customization
+fn create_customize_buffer(renderer: &Renderer, context: &Context, customization: Option<&Customization>) -> Arc<Buffer> {
+    let colors = customization.map(|x| context.get_customization_colors(x)).unwrap_or([[1.; 4]; 7]);
+    let face_paint_flip = customization.map(|x| x.face_paint_flip as u8 as f32).unwrap_or(0.);
+
+    let data = colors
+        .iter()
+        .flatten()
+        .copied()
+        .chain([face_paint_flip, 0., 0., 0.])
+        .collect::<Vec<f32>>();
+
+    let buffer = renderer.buffer_pool.alloc(data.as_bytes().len() as u64);
+    buffer.write(0, data.as_bytes());
+
+    Arc::new(buffer)
+}
+
 pub fn gather_textures(mtrl: &Mtrl, textures: &[Arc<Texture>]) -> HashMap<&'static str, Arc<dyn Resource>> {
     mtrl.parameters()
         .iter()
-        .map(|parameter| {
-            (
-                parameter_type_to_shader_name(&parameter.parameter_type),
+        .filter_map(|parameter| {
+            Some((
+                parameter_type_to_shader_name(parameter.parameter_type())?,
                 textures[parameter.texture_index as usize].clone() as Arc<dyn Resource>,
-            )
+            ))
         })
         .collect::<HashMap<_, _>>()
 }
 
-fn parameter_type_to_shader_name(parameter_type: &MtrlParameterType) -> &'static str {
-    match parameter_type {
-        MtrlParameterType::Normal => "normal_tex",
+// background layer 0 shares name with character textures
+fn parameter_type_to_shader_name(parameter_type: MtrlParameterType) -> Option<&'static str> {
+    Some(match parameter_type {
+        MtrlParameterType::Normal | MtrlParameterType::NormalMap0 => "normal_tex",
         MtrlParameterType::Mask => "mask_tex",
-        MtrlParameterType::Diffuse => "diffuse_tex",
-        MtrlParameterType::Specular => "specular_tex",
+        MtrlParameterType::Diffuse | MtrlParameterType::ColorMap0 => "diffuse_tex",
+        MtrlParameterType::Specular | MtrlParameterType::SpecularMap0 => "specular_tex",
         MtrlParameterType::Catchlight => "catchlight_tex",
-    }
+        MtrlParameterType::ColorMap1 => "diffuse1_tex",
+        MtrlParameterType::NormalMap1 => "normal1_tex",
+        MtrlParameterType::SpecularMap1 => "specular1_tex",
+        MtrlParameterType::Unknown => return None,
+    })
 }
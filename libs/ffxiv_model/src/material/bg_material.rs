@@ -0,0 +1,74 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use hashbrown::HashMap;
+use zerocopy::AsBytes;
+
+use eng::render::{Material, Renderer, Resource};
+use ffxiv_parser::Mtrl;
+
+use crate::{Context, shader_holder::ShaderType};
+
+// crc32 of g_UVScrollTime, uv scroll per second of both layers
+const UV_SCROLL_CONSTANT_ID: u32 = 0x9A69_6A17;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum BgMaterialType {
+    Bg,
+    ColorChange,
+    UvScroll,
+}
+
+pub struct BgMaterial {}
+
+impl BgMaterial {
+    pub fn create(
+        renderer: &Renderer,
+        context: &Context,
+        mtrl: &Mtrl,
+        material_type: BgMaterialType,
+        stain_id: u8,
+        mut resources: HashMap<&'static str, Arc<dyn Resource>>,
+    ) -> Material {
+        let shader = context.shader_holder.shader(ShaderType::Bg);
+
+        if !resources.contains_key("diffuse_tex") {
+            resources.insert("diffuse_tex", context.empty_texture.clone());
+        }
+        if !resources.contains_key("normal_tex") {
+            resources.insert("normal_tex", context.empty_texture.clone());
+        }
+        if !resources.contains_key("specular_tex") {
+            resources.insert("specular_tex", context.empty_texture.clone());
+        }
+
+        // single layer materials blend into themselves
+        if !resources.contains_key("diffuse1_tex") {
+            resources.insert("diffuse1_tex", resources["diffuse_tex"].clone());
+        }
+        if !resources.contains_key("normal1_tex") {
+            resources.insert("normal1_tex", resources["normal_tex"].clone());
+        }
+
+        let color = if material_type == BgMaterialType::ColorChange {
+            context.get_stain_color(stain_id)
+        } else {
+            [1.; 4]
+        };
+        let mut uv_scroll = [0.; 4];
+        if material_type == BgMaterialType::UvScroll
+            && let Some(values) = mtrl.constant(UV_SCROLL_CONSTANT_ID)
+        {
+            let count = values.len().min(4);
+            uv_scroll[..count].copy_from_slice(&values[..count]);
+        }
+
+        let data = [color, uv_scroll];
+        let buffer = renderer.buffer_pool.alloc(data.as_bytes().len() as u64);
+        buffer.write(0, data.as_bytes());
+
+        resources.insert("bg", Arc::new(buffer));
+        resources.insert("scene", context.scene_buffer.clone());
+
+        Material::with_custom_shader(renderer, &resources.into_iter().collect::<Vec<_>>(), shader)
+    }
+}
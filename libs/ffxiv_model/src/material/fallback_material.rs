@@ -0,0 +1,25 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use hashbrown::HashMap;
+
+use eng::render::{Material, Renderer, Resource};
+
+use crate::{Context, shader_holder::ShaderType};
+
+// renders diffuse and normal only, for shaders we don't implement
+pub struct FallbackMaterial {}
+
+impl FallbackMaterial {
+    pub fn create(renderer: &Renderer, context: &Context, mut resources: HashMap<&'static str, Arc<dyn Resource>>) -> Material {
+        let shader = context.shader_holder.shader(ShaderType::Fallback);
+
+        if !resources.contains_key("diffuse_tex") {
+            resources.insert("diffuse_tex", context.empty_texture.clone());
+        }
+        if !resources.contains_key("normal_tex") {
+            resources.insert("normal_tex", context.empty_texture.clone());
+        }
+
+        Material::with_custom_shader(renderer, &resources.into_iter().collect::<Vec<_>>(), shader)
+    }
+}
@@ -15,6 +15,9 @@ impl IrisMaterial {
         if !resources.contains_key("diffuse_tex") {
             resources.insert("diffuse_tex", context.empty_texture.clone());
         }
+        if !resources.contains_key("mask_tex") {
+            resources.insert("mask_tex", context.empty_texture.clone());
+        }
 
         Material::with_custom_shader(renderer, &resources.into_iter().collect::<Vec<_>>(), shader)
     }
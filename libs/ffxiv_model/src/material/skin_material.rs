@@ -8,9 +8,13 @@ use crate::{Context, shader_holder::ShaderType};
 pub struct SkinMaterial {}
 
 impl SkinMaterial {
-    pub fn create(renderer: &Renderer, context: &Context, resources: HashMap<&'static str, Arc<dyn Resource>>) -> Material {
+    pub fn create(renderer: &Renderer, context: &Context, mut resources: HashMap<&'static str, Arc<dyn Resource>>) -> Material {
         let shader = context.shader_holder.shader(ShaderType::Skin);
 
+        if !resources.contains_key("mask_tex") {
+            resources.insert("mask_tex", context.empty_texture.clone());
+        }
+
         let resources = resources.into_iter().collect::<Vec<_>>();
 
         Material::with_custom_shader(renderer, &resources, shader)
@@ -0,0 +1,25 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use hashbrown::HashMap;
+
+use eng::render::{Material, Renderer, Resource};
+
+use crate::{Context, shader_holder::ShaderType};
+
+// overlay on skin, only drawn where the tattoo is
+pub struct TattooMaterial {}
+
+impl TattooMaterial {
+    pub fn create(renderer: &Renderer, context: &Context, mut resources: HashMap<&'static str, Arc<dyn Resource>>) -> Material {
+        let shader = context.shader_holder.shader(ShaderType::Tattoo);
+
+        if !resources.contains_key("diffuse_tex") {
+            resources.insert("diffuse_tex", context.empty_texture.clone());
+        }
+        if !resources.contains_key("normal_tex") {
+            resources.insert("normal_tex", context.empty_texture.clone());
+        }
+
+        Material::with_custom_shader(renderer, &resources.into_iter().collect::<Vec<_>>(), shader)
+    }
+}
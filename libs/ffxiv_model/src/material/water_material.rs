@@ -0,0 +1,22 @@
+use alloc::{sync::Arc, vec::Vec};
+
+use hashbrown::HashMap;
+
+use eng::render::{Material, Renderer, Resource};
+
+use crate::{Context, shader_holder::ShaderType};
+
+pub struct WaterMaterial {}
+
+impl WaterMaterial {
+    pub fn create(renderer: &Renderer, context: &Context, mut resources: HashMap<&'static str, Arc<dyn Resource>>) -> Material {
+        let shader = context.shader_holder.shader(ShaderType::Water);
+
+        if !resources.contains_key("normal_tex") {
+            resources.insert("normal_tex", context.empty_texture.clone());
+        }
+        resources.insert("scene", context.scene_buffer.clone());
+
+        Material::with_custom_shader(renderer, &resources.into_iter().collect::<Vec<_>>(), shader)
+    }
+}
@@ -3,17 +3,19 @@ use alloc::{format, string::String, sync::Arc, vec::Vec};
 use futures::{FutureExt, future};
 
 use eng::render::{Renderer, Texture};
-use ffxiv_parser::{Mdl, Mtrl};
+use ffxiv_parser::{Imc, Mdl, Mtrl};
 use sqpack::{Package, Result, SqPackReaderError};
 
 use crate::constants::{BodyId, ModelPart};
 use crate::context::Context;
 use crate::customization::Customization;
 use crate::equipment::Equipment;
+use crate::object_model::ObjectModel;
 
 pub struct ModelData {
     pub mdl: Mdl,
     pub mtrls: Vec<(Mtrl, Vec<Arc<Texture>>)>,
+    pub decal: Option<Arc<Texture>>,
 }
 
 pub struct EquipmentModelData {
@@ -21,8 +23,23 @@ pub struct EquipmentModelData {
     pub original_body_id: BodyId,
     pub deformed_body_id: BodyId,
     pub stain_id: u8,
+    pub visibility_mask: usize,
 }
 
+pub struct BodyModelData {
+    pub model_data: ModelData,
+    pub original_body_id: BodyId,
+    pub deformed_body_id: BodyId,
+}
+
+pub struct ObjectModelData {
+    pub model_data: ModelData,
+    pub stain_id: u8,
+    pub visibility_mask: usize,
+}
+
+const IMC_FULL_ATTRIBUTE_MASK: usize = 0x3ff;
+
 pub struct ModelReader {}
 
 impl ModelReader {
@@ -36,15 +53,33 @@ impl ModelReader {
     ) -> Result<EquipmentModelData> {
         let deformed_body_id = context.get_deformed_body_id(customization.body_id, equipment.model_id, equipment_part);
 
+        // accessories use same layout as equipments under different directory and prefix
+        let (base_path, prefix) = if equipment_part.is_accessory() {
+            ("chara/accessory", 'a')
+        } else {
+            ("chara/equipment", 'e')
+        };
+
+        let (material_id, visibility_mask) = Self::read_imc(
+            package,
+            &format!(
+                "{base_path}/{prefix}{equipment_id:04}/{prefix}{equipment_id:04}.imc",
+                equipment_id = equipment.model_id
+            ),
+            equipment.variant_id,
+            equipment_part.imc_part_index(),
+        )
+        .await?;
+
         let mdl_path = format!(
-            "chara/equipment/e{equipment_id:04}/model/c{body_id:04}e{equipment_id:04}_{equipment_part}.mdl",
+            "{base_path}/{prefix}{equipment_id:04}/model/c{body_id:04}{prefix}{equipment_id:04}_{equipment_part}.mdl",
             equipment_id = equipment.model_id,
-            body_id = deformed_body_id as u16,
+            body_id = deformed_body_id.id(),
             equipment_part = equipment_part.as_path_str()
         );
 
         let model_data = Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
-            Self::convert_equipment_material_path(material_path, customization, equipment.model_id, equipment.variant_id)
+            Self::convert_equipment_material_path(material_path, customization, base_path, prefix, equipment.model_id, material_id)
         })
         .await?;
 
@@ -53,38 +88,125 @@ impl ModelReader {
             original_body_id: customization.body_id,
             deformed_body_id,
             stain_id: equipment.stain_id,
+            visibility_mask,
+        })
+    }
+
+    pub async fn read_object(renderer: &Renderer, package: &dyn Package, object_model: &ObjectModel, context: &Context) -> Result<ObjectModelData> {
+        let (material_id, visibility_mask) =
+            Self::read_imc(package, &object_model.imc_path(), object_model.variant_id, object_model.imc_part_index()).await?;
+
+        let model_data = Self::read_mdl(renderer, package, &object_model.mdl_path(), context, |material_path| {
+            object_model.material_path(material_path, material_id)
+        })
+        .await?;
+
+        Ok(ObjectModelData {
+            model_data,
+            stain_id: object_model.stain_id,
+            visibility_mask,
+        })
+    }
+
+    // material id and visibility mask. without imc entry, variant id is used as material id and every attribute is visible.
+    async fn read_imc(package: &dyn Package, path: &str, variant_id: u8, part_index: Option<usize>) -> Result<(u8, usize)> {
+        let imc = match Imc::new(package, path).await {
+            Ok(x) => Some(x),
+            Err(SqPackReaderError::NoSuchFile) => None,
+            Err(err) => return Err(err),
+        };
+
+        let imc_entry = imc
+            .as_ref()
+            .zip(part_index)
+            .and_then(|(imc, part_index)| imc.get(variant_id as u16, part_index));
+
+        Ok(match imc_entry {
+            Some(x) => (x.material_id, x.attribute_mask() as usize),
+            None => (variant_id, IMC_FULL_ATTRIBUTE_MASK),
         })
     }
 
+    // most bodies share the model of their deformer parent. None if no body in the deformer tree has the part.
+    pub async fn read_body(
+        renderer: &Renderer,
+        package: &dyn Package,
+        customization: &Customization,
+        body_part: ModelPart,
+        context: &Context,
+    ) -> Result<Option<BodyModelData>> {
+        for deformed_body_id in context.get_deformer_chain(customization.body_id) {
+            let mdl_path = format!(
+                "chara/human/c{body_id:04}/obj/body/b{body_type:04}/model/c{body_id:04}b{body_type:04}_{body_part}.mdl",
+                body_id = deformed_body_id.id(),
+                body_type = customization.body_type,
+                body_part = body_part.as_path_str()
+            );
+
+            let model_data = match Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
+                Self::convert_body_material_path(material_path, customization)
+            })
+            .await
+            {
+                Ok(x) => x,
+                Err(SqPackReaderError::NoSuchFile) => continue,
+                Err(err) => return Err(err),
+            };
+
+            return Ok(Some(BodyModelData {
+                model_data,
+                original_body_id: customization.body_id,
+                deformed_body_id,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    pub async fn read_tail(renderer: &Renderer, package: &dyn Package, customization: &Customization, context: &Context) -> Result<ModelData> {
+        Self::read_human_part(renderer, package, customization, context, "tail", 't', customization.tail_id, "til").await
+    }
+
+    pub async fn read_ear(renderer: &Renderer, package: &dyn Package, customization: &Customization, context: &Context) -> Result<ModelData> {
+        Self::read_human_part(renderer, package, customization, context, "zear", 'z', customization.ear_id, "zer").await
+    }
+
     pub async fn read_face(renderer: &Renderer, package: &dyn Package, customization: &Customization, context: &Context) -> Result<ModelData> {
         let mdl_path = format!(
             "chara/human/c{body_id:04}/obj/face/f{face_id:04}/model/c{body_id:04}f{face_id:04}_fac.mdl",
-            body_id = customization.body_id as u16,
+            body_id = customization.body_id.id(),
             face_id = customization.face_id
         );
 
-        Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
+        let mut model_data = Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
             format!(
                 "chara/human/c{body_id:04}/obj/face/f{face_id:04}/material/mt_c{body_id:04}f{face_id:04}{path}",
-                body_id = customization.body_id as u16,
+                body_id = customization.body_id.id(),
                 face_id = customization.face_id,
                 path = &material_path[14..]
             )
         })
-        .await
+        .await?;
+
+        if customization.face_paint_id != 0 {
+            let decal_path = format!("chara/common/texture/decal_face/_decal_{}.tex", customization.face_paint_id);
+            model_data.decal = Some(context.texture_cache.get_or_read(renderer, package, decal_path).await?);
+        }
+
+        Ok(model_data)
     }
 
     pub async fn read_hair(renderer: &Renderer, package: &dyn Package, customization: &Customization, context: &Context) -> Result<ModelData> {
         let mdl_path = format!(
             "chara/human/c{body_id:04}/obj/hair/h{hair_id:04}/model/c{body_id:04}h{hair_id:04}_hir.mdl",
-            body_id = customization.body_id as u16,
+            body_id = customization.body_id.id(),
             hair_id = customization.hair_id
         );
 
         Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
             format!(
                 "chara/human/c{body_id:04}/obj/hair/h{hair_id:04}/material/v{hair_variant_id:04}/mt_c{body_id:04}h{hair_id:04}{path}",
-                body_id = customization.body_id as u16,
+                body_id = customization.body_id.id(),
                 hair_id = customization.hair_id,
                 hair_variant_id = customization.hair_variant_id,
                 path = &material_path[14..]
@@ -93,6 +215,31 @@ impl ModelReader {
         .await
     }
 
+    // tail and ear share layout, materials are per variant like body
+    #[allow(clippy::too_many_arguments)]
+    async fn read_human_part(
+        renderer: &Renderer,
+        package: &dyn Package,
+        customization: &Customization,
+        context: &Context,
+        kind: &str,
+        prefix: char,
+        id: u8,
+        part: &str,
+    ) -> Result<ModelData> {
+        let body_id = customization.body_id.id();
+        let mdl_path = format!("chara/human/c{body_id:04}/obj/{kind}/{prefix}{id:04}/model/c{body_id:04}{prefix}{id:04}_{part}.mdl");
+
+        Self::read_mdl(renderer, package, &mdl_path, context, |material_path| {
+            format!(
+                "chara/human/c{body_id:04}/obj/{kind}/{prefix}{id:04}/material/v{variant_id:04}/mt_c{body_id:04}{prefix}{id:04}{path}",
+                variant_id = customization.body_variant_id,
+                path = &material_path[14..]
+            )
+        })
+        .await
+    }
+
     async fn read_mdl<F>(renderer: &Renderer, package: &dyn Package, mdl_path: &str, context: &Context, material_path_fetcher: F) -> Result<ModelData>
     where
         F: Fn(&str) -> String,
@@ -114,20 +261,31 @@ impl ModelReader {
         }))
         .await?;
 
-        Ok(ModelData { mdl, mtrls })
+        Ok(ModelData { mdl, mtrls, decal: None })
     }
 
-    fn convert_equipment_material_path(material_path: &str, customization: &Customization, equipment_id: u16, equipment_variant_id: u8) -> String {
+    fn convert_equipment_material_path(
+        material_path: &str,
+        customization: &Customization,
+        base_path: &str,
+        prefix: char,
+        equipment_id: u16,
+        material_id: u8,
+    ) -> String {
         if material_path.chars().nth(9).unwrap() == 'b' {
-            format!(
-                "chara/human/c{body_id:04}/obj/body/b{body_type:04}/material/v{variant_id:04}/mt_c{body_id:04}b{body_type:04}{path}",
-                body_id = customization.body_id as u16,
-                body_type = customization.body_type,
-                variant_id = customization.body_variant_id,
-                path = &material_path[14..]
-            )
+            Self::convert_body_material_path(material_path, customization)
         } else {
-            format!("chara/equipment/e{equipment_id:04}/material/v{equipment_variant_id:04}{material_path}")
+            format!("{base_path}/{prefix}{equipment_id:04}/material/v{material_id:04}{material_path}")
         }
     }
+
+    fn convert_body_material_path(material_path: &str, customization: &Customization) -> String {
+        format!(
+            "chara/human/c{body_id:04}/obj/body/b{body_type:04}/material/v{variant_id:04}/mt_c{body_id:04}b{body_type:04}{path}",
+            body_id = customization.body_id.id(),
+            body_type = customization.body_type,
+            variant_id = customization.body_variant_id,
+            path = &material_path[14..]
+        )
+    }
 }
@@ -0,0 +1,49 @@
+use alloc::vec::Vec;
+
+use glam::Mat4;
+
+use eng::{
+    ecs::{HierarchyExt, World},
+    render::Renderer,
+};
+use sqpack::{Package, Result};
+
+use crate::{
+    character_part::{BoneTable, CharacterPart},
+    context::Context,
+    model_reader::ModelReader,
+    object_model::ObjectModel,
+};
+
+// weapon, monster or demihuman model
+pub struct Object {
+    bone_tables: Vec<BoneTable>,
+}
+
+impl Object {
+    pub async fn load(world: &mut World, package: &dyn Package, context: &Context, object_model: ObjectModel) -> Result<Self> {
+        let entity = world.spawn().entity();
+
+        let renderer = world.resource::<Renderer>().unwrap();
+
+        let object_model_data = ModelReader::read_object(renderer, package, &object_model, context).await?;
+        let part = CharacterPart::load_object_model(renderer, object_model_data, context);
+
+        let mut bone_tables = Vec::new();
+        for (bundle, bone_table) in part {
+            let part_entity = world.spawn_bundle(bundle);
+            world.add_child(entity, part_entity);
+
+            bone_tables.push(bone_table);
+        }
+
+        Ok(Self { bone_tables })
+    }
+
+    // places whole model with given matrix
+    pub fn update(&self, transform: Mat4) {
+        for bone_table in &self.bone_tables {
+            bone_table.update_rigid(transform);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::{format, string::String};
+
+use crate::constants::ModelPart;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum ObjectModelType {
+    Weapon,
+    Monster,
+    Demihuman(ModelPart),
+}
+
+// non-human model. body_id is equipment id on demihuman.
+pub struct ObjectModel {
+    pub(crate) model_type: ObjectModelType,
+    pub(crate) model_id: u16,
+    pub(crate) body_id: u16,
+    pub(crate) variant_id: u8,
+    pub(crate) stain_id: u8,
+}
+
+impl ObjectModel {
+    pub fn weapon(model_id: u16, body_id: u16, variant_id: u8, stain_id: u8) -> Self {
+        Self {
+            model_type: ObjectModelType::Weapon,
+            model_id,
+            body_id,
+            variant_id,
+            stain_id,
+        }
+    }
+
+    // mounts are also stored as monster
+    pub fn monster(model_id: u16, body_id: u16, variant_id: u8) -> Self {
+        Self {
+            model_type: ObjectModelType::Monster,
+            model_id,
+            body_id,
+            variant_id,
+            stain_id: 0,
+        }
+    }
+
+    pub fn demihuman(model_id: u16, equipment_id: u16, equipment_part: ModelPart, variant_id: u8, stain_id: u8) -> Self {
+        Self {
+            model_type: ObjectModelType::Demihuman(equipment_part),
+            model_id,
+            body_id: equipment_id,
+            variant_id,
+            stain_id,
+        }
+    }
+
+    pub(crate) fn base_path(&self) -> String {
+        match self.model_type {
+            ObjectModelType::Weapon => format!("chara/weapon/w{:04}/obj/body/b{:04}", self.model_id, self.body_id),
+            ObjectModelType::Monster => format!("chara/monster/m{:04}/obj/body/b{:04}", self.model_id, self.body_id),
+            ObjectModelType::Demihuman(_) => format!("chara/demihuman/d{:04}/obj/equipment/e{:04}", self.model_id, self.body_id),
+        }
+    }
+
+    pub(crate) fn mdl_path(&self) -> String {
+        let base_path = self.base_path();
+
+        match self.model_type {
+            ObjectModelType::Weapon => format!("{base_path}/model/w{:04}b{:04}.mdl", self.model_id, self.body_id),
+            ObjectModelType::Monster => format!("{base_path}/model/m{:04}b{:04}.mdl", self.model_id, self.body_id),
+            ObjectModelType::Demihuman(part) => format!("{base_path}/model/d{:04}e{:04}_{}.mdl", self.model_id, self.body_id, part.as_path_str()),
+        }
+    }
+
+    pub(crate) fn imc_path(&self) -> String {
+        let base_path = self.base_path();
+
+        match self.model_type {
+            ObjectModelType::Weapon | ObjectModelType::Monster => format!("{base_path}/b{:04}.imc", self.body_id),
+            ObjectModelType::Demihuman(_) => format!("{base_path}/e{:04}.imc", self.body_id),
+        }
+    }
+
+    pub(crate) fn imc_part_index(&self) -> Option<usize> {
+        match self.model_type {
+            ObjectModelType::Weapon | ObjectModelType::Monster => Some(0),
+            ObjectModelType::Demihuman(part) => part.imc_part_index(),
+        }
+    }
+
+    // some models refer materials by absolute path
+    pub(crate) fn material_path(&self, material_path: &str, material_id: u8) -> String {
+        if material_path.starts_with('/') {
+            format!("{}/material/v{material_id:04}{material_path}", self.base_path())
+        } else {
+            material_path.into()
+        }
+    }
+}
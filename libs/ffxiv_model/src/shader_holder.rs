@@ -11,6 +11,11 @@ pub enum ShaderType {
     Iris,
     Hair,
     Skin,
+    Fallback,
+    Bg,
+    Water,
+    Crystal,
+    Tattoo,
 }
 
 pub struct ShaderHolder {
@@ -25,6 +30,11 @@ impl ShaderHolder {
                 (ShaderType::Iris, Arc::new(Self::load_iris_shader(renderer))),
                 (ShaderType::Hair, Arc::new(Self::load_hair_shader(renderer))),
                 (ShaderType::Skin, Arc::new(Self::load_skin_shader(renderer))),
+                (ShaderType::Fallback, Arc::new(Self::load_fallback_shader(renderer))),
+                (ShaderType::Bg, Arc::new(Self::load_bg_shader(renderer))),
+                (ShaderType::Water, Arc::new(Self::load_water_shader(renderer))),
+                (ShaderType::Crystal, Arc::new(Self::load_crystal_shader(renderer))),
+                (ShaderType::Tattoo, Arc::new(Self::load_tattoo_shader(renderer))),
             ]),
         }
     }
@@ -35,9 +45,10 @@ impl ShaderHolder {
 
     fn compose_shader(fragment: &str) -> String {
         format!(
-            "{}\n{}\n{}",
+            "{}\n{}\n{}\n{}",
             include_str!("../shaders/vertex.wgsl"),
             include_str!("../shaders/frag_common.wgsl"),
+            include_str!("../shaders/customize.wgsl"),
             fragment
         )
     }
@@ -65,4 +76,34 @@ impl ShaderHolder {
 
         Shader::new(renderer, &shader)
     }
+
+    fn load_fallback_shader(renderer: &Renderer) -> Shader {
+        let shader = Self::compose_shader(include_str!("../shaders/fallback.wgsl"));
+
+        Shader::new(renderer, &shader)
+    }
+
+    fn load_bg_shader(renderer: &Renderer) -> Shader {
+        let shader = Self::compose_shader(include_str!("../shaders/bg.wgsl"));
+
+        Shader::new(renderer, &shader)
+    }
+
+    fn load_water_shader(renderer: &Renderer) -> Shader {
+        let shader = Self::compose_shader(include_str!("../shaders/water.wgsl"));
+
+        Shader::new(renderer, &shader)
+    }
+
+    fn load_crystal_shader(renderer: &Renderer) -> Shader {
+        let shader = Self::compose_shader(include_str!("../shaders/crystal.wgsl"));
+
+        Shader::new(renderer, &shader)
+    }
+
+    fn load_tattoo_shader(renderer: &Renderer) -> Shader {
+        let shader = Self::compose_shader(include_str!("../shaders/tattoo.wgsl"));
+
+        Shader::new(renderer, &shader)
+    }
 }
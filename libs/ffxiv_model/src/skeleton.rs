@@ -0,0 +1,118 @@
+use alloc::{format, string::String, vec::Vec};
+
+use glam::Mat4;
+use hashbrown::HashMap;
+
+use ffxiv_parser::Sklb;
+use havok_parser::{HavokAnimationBinding, HavokAnimationContainer, HavokReader, HavokSkeleton, HavokTransform};
+use sqpack::{Package, Result, SqPackReaderError};
+
+use crate::constants::{BodyId, ModelPart};
+use crate::context::Context;
+
+pub struct Skeleton {
+    skeleton: HavokSkeleton,
+    inverse_bind_matrices: Vec<Mat4>,
+}
+
+impl Skeleton {
+    // base skeleton with face, hair and helmet skeletons attached. parts without skeleton are skipped.
+    pub async fn new(package: &dyn Package, context: &Context, body_id: BodyId, face_id: u8, hair_id: u8, met_id: Option<u16>) -> Result<Self> {
+        let mut skeleton = Self::read_havok(package, &Self::base_path(body_id)).await?;
+
+        let parts = [
+            (ModelPart::Fac, "face", 'f', Some(face_id as u16)),
+            (ModelPart::Hir, "hair", 'h', Some(hair_id as u16)),
+            (ModelPart::Met, "met", 'm', met_id),
+        ];
+
+        for (model_part, kind, prefix, set_id) in parts {
+            let Some(skeleton_id) = set_id.and_then(|x| context.get_extra_skeleton_id(body_id, model_part, x)) else {
+                continue;
+            };
+
+            let partial_path = format!(
+                "chara/human/c{body_id:04}/skeleton/{kind}/{prefix}{skeleton_id:04}/skl_c{body_id:04}{prefix}{skeleton_id:04}.sklb",
+                body_id = body_id.id()
+            );
+            match Self::read_havok(package, &partial_path).await {
+                Ok(partial) => skeleton.merge(&partial),
+                Err(SqPackReaderError::NoSuchFile) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Self::from_havok(skeleton))
+    }
+
+    pub fn from_havok(skeleton: HavokSkeleton) -> Self {
+        let inverse_bind_matrices = skeleton
+            .to_model_space(&skeleton.reference_pose)
+            .iter()
+            .map(|x| Self::to_matrix(x).inverse())
+            .collect::<Vec<_>>();
+
+        Self {
+            skeleton,
+            inverse_bind_matrices,
+        }
+    }
+
+    // time in milliseconds
+    pub fn sample_pose(&self, binding: &HavokAnimationBinding, time: f32) -> Vec<HavokTransform> {
+        binding.sample_pose(&self.skeleton.reference_pose, time)
+    }
+
+    pub fn reference_pose(&self) -> &[HavokTransform] {
+        &self.skeleton.reference_pose
+    }
+
+    // model space matrix of bone in given pose
+    pub fn bone_matrix(&self, local_pose: &[HavokTransform], bone_name: &str) -> Option<Mat4> {
+        let index = self.skeleton.bone_names.iter().position(|x| x == bone_name)?;
+
+        Some(Self::to_matrix(&self.skeleton.to_model_space(local_pose)[index]))
+    }
+
+    // pose × inverse bind, keyed by bone name
+    pub fn skinning_matrices(&self, local_pose: &[HavokTransform]) -> HashMap<&str, Mat4> {
+        self.skeleton
+            .to_model_space(local_pose)
+            .iter()
+            .zip(self.inverse_bind_matrices.iter())
+            .zip(self.skeleton.bone_names.iter())
+            .map(|((model, inverse_bind), name)| (name.as_str(), Self::to_matrix(model) * *inverse_bind))
+            .collect()
+    }
+
+    fn base_path(body_id: BodyId) -> String {
+        format!(
+            "chara/human/c{body_id:04}/skeleton/base/b0001/skl_c{body_id:04}b0001.sklb",
+            body_id = body_id.id()
+        )
+    }
+
+    async fn read_havok(package: &dyn Package, path: &str) -> Result<HavokSkeleton> {
+        let sklb = Sklb::new(package, path).await?;
+
+        let container = HavokReader::read(sklb.hkx_data()).and_then(|x| HavokAnimationContainer::new(x.find_object_by_type("hkaAnimationContainer")));
+        let skeleton = container.map(|x| x.skeletons.into_iter().next());
+        match skeleton {
+            Ok(Some(x)) => Ok(x),
+            Ok(None) => {
+                log::error!("No skeleton in {path}");
+
+                Err(SqPackReaderError::ReadError)
+            }
+            Err(err) => {
+                log::error!("Can't read skeleton {path}: {err:?}");
+
+                Err(SqPackReaderError::ReadError)
+            }
+        }
+    }
+
+    fn to_matrix(transform: &HavokTransform) -> Mat4 {
+        Mat4::from_cols_array(&transform.to_matrix())
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::{string::String, sync::Arc, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc, vec, vec::Vec};
 
 use futures::channel::oneshot;
 use hashbrown::HashMap;
@@ -6,58 +6,151 @@ use spinning_top::Spinlock;
 
 use eng::render::{CompressedTextureFormat, Renderer, Texture, TextureFormat};
 use ffxiv_parser::{Tex, TextureType};
-use sqpack::{Package, Result};
+use sqpack::{Package, Result, SqPackReaderError};
+
+// textures still used by materials are kept alive by them, cache only drops its own reference
+const DEFAULT_CAPACITY: usize = 256 * 1024 * 1024;
+
+struct CacheEntry {
+    texture: Arc<Texture>,
+    size: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    entries: HashMap<String, CacheEntry>,
+    total_size: usize,
+    clock: u64,
+}
+
+// outcome of fetching shared with waiters. errors we can't hand out twice are fetched again by each waiter.
+#[derive(Clone)]
+enum FetchResult {
+    Texture(Arc<Texture>),
+    NoSuchFile,
+    Retry,
+}
+
+type Waiter = oneshot::Sender<FetchResult>;
 
 pub struct TextureCache {
-    waiters: Spinlock<HashMap<String, Vec<oneshot::Sender<bool>>>>,
-    textures: Spinlock<HashMap<String, Arc<Texture>>>,
+    waiters: Spinlock<HashMap<String, Vec<Waiter>>>,
+    textures: Spinlock<CacheEntries>,
+    capacity: usize,
 }
 
 impl TextureCache {
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    // capacity is in bytes of texture data
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             waiters: Spinlock::new(HashMap::new()),
-            textures: Spinlock::new(HashMap::new()),
+            textures: Spinlock::new(CacheEntries::default()),
+            capacity,
         }
     }
 
+    pub fn clear(&self) {
+        *self.textures.lock() = CacheEntries::default();
+    }
+
     pub async fn get_or_read(&self, renderer: &Renderer, package: &dyn Package, texture_path: String) -> Result<Arc<Texture>> {
-        {
-            let textures = self.textures.lock();
-            if let Some(x) = textures.get(&texture_path) {
-                return Ok(x.clone());
-            }
+        if let Some(x) = self.get(&texture_path) {
+            return Ok(x);
         }
-        let should_fetch;
+
         let (tx, rx) = oneshot::channel();
-        {
+        let should_fetch = {
             let mut waiters = self.waiters.lock();
-            if waiters.contains_key(&texture_path) {
-                waiters.get_mut(&texture_path).unwrap().push(tx);
-                should_fetch = false;
+            if let Some(x) = waiters.get_mut(&texture_path) {
+                x.push(tx);
+                false
             } else {
-                waiters.insert(texture_path.clone(), vec![tx]);
-                should_fetch = true;
+                waiters.insert(texture_path.clone(), vec![]);
+                true
             }
-        }
+        };
         if should_fetch {
-            let tex = Tex::new(package, &texture_path).await?;
+            return self.fetch(renderer, package, texture_path).await;
+        }
+
+        match rx.await {
+            Ok(FetchResult::Texture(texture)) => Ok(texture),
+            Ok(FetchResult::NoSuchFile) => Err(SqPackReaderError::NoSuchFile),
+            // fetching failed with other error or was cancelled. retry goes through fetch again, so it's shared and cached.
+            Ok(FetchResult::Retry) | Err(_) => Box::pin(self.get_or_read(renderer, package, texture_path)).await,
+        }
+    }
+
+    async fn fetch(&self, renderer: &Renderer, package: &dyn Package, texture_path: String) -> Result<Arc<Texture>> {
+        let mut guard = WaiterGuard {
+            waiters: &self.waiters,
+            texture_path: &texture_path,
+            armed: true,
+        };
+
+        let result = Tex::new(package, &texture_path).await.map(|tex| {
             let texture = Arc::new(Self::load_texture(renderer, &tex));
+            self.insert(texture_path.clone(), texture.clone(), tex.data(0).len());
 
-            {
-                let mut textures = self.textures.lock();
-                textures.insert(texture_path.clone(), texture);
-            }
+            texture
+        });
 
-            let waiters = self.waiters.lock().remove(&texture_path).unwrap();
-            for waiter in waiters {
-                waiter.send(true).unwrap();
-            }
+        guard.armed = false;
+        let fetch_result = match &result {
+            Ok(texture) => FetchResult::Texture(texture.clone()),
+            Err(SqPackReaderError::NoSuchFile) => FetchResult::NoSuchFile,
+            Err(_) => FetchResult::Retry,
+        };
+        self.notify_waiters(&texture_path, fetch_result);
+
+        result
+    }
+
+    fn notify_waiters(&self, texture_path: &str, fetch_result: FetchResult) {
+        let waiters = self.waiters.lock().remove(texture_path).unwrap_or_default();
+        for waiter in waiters {
+            let _ = waiter.send(fetch_result.clone());
         }
+    }
 
-        let _ = rx.await;
+    fn get(&self, texture_path: &str) -> Option<Arc<Texture>> {
+        let mut textures = self.textures.lock();
+        textures.clock += 1;
 
-        Ok(self.textures.lock().get(&texture_path).unwrap().clone())
+        let clock = textures.clock;
+        let entry = textures.entries.get_mut(texture_path)?;
+        entry.last_used = clock;
+
+        Some(entry.texture.clone())
+    }
+
+    fn insert(&self, texture_path: String, texture: Arc<Texture>, size: usize) {
+        let mut textures = self.textures.lock();
+        textures.clock += 1;
+
+        let last_used = textures.clock;
+        textures.total_size += size;
+        if let Some(old) = textures.entries.insert(texture_path, CacheEntry { texture, size, last_used }) {
+            textures.total_size -= old.size;
+        }
+
+        // evict least recently used, but keep the one just inserted
+        while textures.total_size > self.capacity && textures.entries.len() > 1 {
+            let oldest = textures
+                .entries
+                .iter()
+                .min_by_key(|(_, x)| x.last_used)
+                .map(|(path, _)| path.clone())
+                .unwrap();
+
+            let entry = textures.entries.remove(&oldest).unwrap();
+            textures.total_size -= entry.size;
+        }
     }
 
     fn load_texture(renderer: &Renderer, tex: &Tex) -> Texture {
@@ -83,3 +176,56 @@ impl TextureCache {
         }
     }
 }
+
+// releases waiters if fetching future is dropped before completion
+struct WaiterGuard<'a> {
+    waiters: &'a Spinlock<HashMap<String, Vec<Waiter>>>,
+    texture_path: &'a str,
+    armed: bool,
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.waiters.lock().remove(self.texture_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use futures::channel::oneshot;
+
+    use super::{FetchResult, TextureCache, WaiterGuard};
+
+    #[test]
+    fn test_failure_shared_with_waiters() {
+        let cache = TextureCache::new();
+
+        let (tx, mut rx) = oneshot::channel();
+        cache.waiters.lock().insert("test.tex".into(), vec![tx]);
+        cache.notify_waiters("test.tex", FetchResult::NoSuchFile);
+
+        assert!(matches!(rx.try_recv(), Ok(Some(FetchResult::NoSuchFile))));
+        assert!(cache.waiters.lock().is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_fetch_releases_waiters() {
+        let cache = TextureCache::new();
+
+        let (tx, mut rx) = oneshot::channel();
+        cache.waiters.lock().insert("test.tex".into(), vec![tx]);
+        drop(WaiterGuard {
+            waiters: &cache.waiters,
+            texture_path: "test.tex",
+            armed: true,
+        });
+
+        // waiter is woken up instead of hanging, and next reader becomes the fetcher
+        assert!(rx.try_recv().is_err());
+        assert!(cache.waiters.lock().is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use sqpack::{Package, Result};
+use util::{cast, cast_array};
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RacialScaling {
+    pub male_min_size: f32,
+    pub male_max_size: f32,
+    pub male_min_tail: f32,
+    pub male_max_tail: f32,
+    pub female_min_size: f32,
+    pub female_max_size: f32,
+    pub female_min_tail: f32,
+    pub female_max_tail: f32,
+    pub bust_min: [f32; 3],
+    pub bust_max: [f32; 3],
+}
+
+// CharaMakeParameter
+pub struct Cmp {
+    data: Vec<u8>,
+}
+
+impl Cmp {
+    const RACIAL_SCALING_OFFSET: usize = 0x2A800;
+
+    pub async fn new(package: &dyn Package) -> Result<Self> {
+        let data = package.read_file("chara/xls/charamake/human.cmp").await?;
+
+        Ok(Self { data })
+    }
+
+    // rgba colors used by character customization
+    pub fn colors(&self) -> &[[u8; 4]] {
+        cast_array::<[u8; 4]>(&self.data[..Self::RACIAL_SCALING_OFFSET])
+    }
+
+    // race_id, tribe_id from Race, Tribe sheet. both start from 1.
+    pub fn racial_scaling(&self, race_id: u8, tribe_id: u8) -> Option<&RacialScaling> {
+        // tribes are numbered sequentially, two per race
+        let clan = tribe_id.checked_sub(1)? % 2;
+        let index = (race_id as usize).checked_sub(1)? * 10 + clan as usize;
+
+        let offset = Self::RACIAL_SCALING_OFFSET + index * size_of::<RacialScaling>();
+        if offset + size_of::<RacialScaling>() > self.data.len() {
+            return None;
+        }
+
+        Some(cast::<RacialScaling>(&self.data[offset..]))
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use bitflags::bitflags;
+
+use sqpack::{Package, Result};
+use util::cast_array;
+
+bitflags! {
+    pub struct EqpEntry: u64 {
+        const BODY_ENABLED = 1 << 0;
+        const BODY_HIDE_WAIST = 1 << 1;
+        const BODY_HIDE_THIGHS = 1 << 2;
+        const BODY_HIDE_SMALL_GLOVES = 1 << 3;
+        const BODY_HIDE_MEDIUM_GLOVES = 1 << 5;
+        const BODY_HIDE_LARGE_GLOVES = 1 << 6;
+        const BODY_HIDE_GORGET = 1 << 7;
+        const BODY_SHOW_LEG = 1 << 8;
+        const BODY_SHOW_HAND = 1 << 9;
+        const BODY_SHOW_HEAD = 1 << 10;
+        const BODY_SHOW_NECKLACE = 1 << 11;
+        const BODY_SHOW_BRACELET = 1 << 12;
+        const BODY_SHOW_TAIL = 1 << 13;
+        const BODY_DISABLE_BREAST_PHYSICS = 1 << 14;
+        const BODY_USES_EVP_TABLE = 1 << 15;
+
+        const LEGS_ENABLED = 1 << 16;
+        const LEGS_HIDE_KNEE_PADS = 1 << 17;
+        const LEGS_HIDE_SMALL_BOOTS = 1 << 18;
+        const LEGS_HIDE_MEDIUM_BOOTS = 1 << 19;
+        const LEGS_SHOW_FOOT = 1 << 21;
+        const LEGS_SHOW_TAIL = 1 << 22;
+
+        const HANDS_ENABLED = 1 << 24;
+        const HANDS_HIDE_ELBOW = 1 << 25;
+        const HANDS_HIDE_FOREARM = 1 << 26;
+        const HANDS_SHOW_BRACELET = 1 << 28;
+        const HANDS_SHOW_LEFT_RING = 1 << 29;
+        const HANDS_SHOW_RIGHT_RING = 1 << 30;
+
+        const FEET_ENABLED = 1 << 32;
+        const FEET_HIDE_KNEE = 1 << 33;
+        const FEET_HIDE_CALF = 1 << 34;
+        const FEET_HIDE_ANKLE = 1 << 35;
+
+        const HEAD_ENABLED = 1 << 40;
+        const HEAD_HIDE_SCALP = 1 << 41;
+        const HEAD_HIDE_HAIR = 1 << 42;
+        const HEAD_SHOW_HAIR_OVERRIDE = 1 << 43;
+        const HEAD_HIDE_NECK = 1 << 44;
+        const HEAD_SHOW_NECKLACE = 1 << 45;
+        const HEAD_SHOW_EARRINGS = 1 << 47;
+        const HEAD_SHOW_EARRINGS_HUMAN = 1 << 48;
+        const HEAD_SHOW_EARRINGS_AURA = 1 << 49;
+        const HEAD_SHOW_EAR_HUMAN = 1 << 50;
+        const HEAD_SHOW_EAR_MIQOTE = 1 << 51;
+        const HEAD_SHOW_EAR_AURA = 1 << 52;
+        const HEAD_SHOW_EAR_VIERA = 1 << 53;
+        const HEAD_SHOW_HROTHGAR_HAT = 1 << 56;
+        const HEAD_SHOW_VIERA_HAT = 1 << 57;
+        const HEAD_USES_EVP_TABLE = 1 << 58;
+    }
+}
+
+// entries are stored in blocks of 160 sets. the first entry doubles as the bitmask of present blocks.
+pub(crate) fn read_block_entry(data: &[u8], set_id: u16) -> Option<u64> {
+    const BLOCK_SIZE: usize = 160;
+
+    let entries = cast_array::<u64>(data);
+    let block_mask = entries[0];
+
+    let block_index = set_id as usize / BLOCK_SIZE;
+    if block_index >= 64 || block_mask & (1 << block_index) == 0 {
+        return None;
+    }
+
+    let present_blocks_before = (block_mask & ((1 << block_index) - 1)).count_ones() as usize;
+
+    Some(entries[present_blocks_before * BLOCK_SIZE + set_id as usize % BLOCK_SIZE])
+}
+
+// EquipmentParameter
+pub struct Eqp {
+    data: Vec<u8>,
+}
+
+impl Eqp {
+    pub async fn new(package: &dyn Package) -> Result<Self> {
+        let data = package.read_file("chara/xls/equipmentparameter/equipmentparameter.eqp").await?;
+
+        Ok(Self { data })
+    }
+
+    pub fn get(&self, set_id: u16) -> EqpEntry {
+        let raw = read_block_entry(&self.data, set_id).unwrap_or(0);
+
+        EqpEntry::from_bits_truncate(raw)
+    }
+}
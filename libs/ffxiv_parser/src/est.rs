@@ -0,0 +1,61 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use sqpack::{Package, Result};
+use util::{cast, cast_array};
+
+#[repr(C)]
+struct ExtraSkeletonTableHeader {
+    count: u32,
+}
+
+#[repr(C)]
+struct ExtraSkeletonTableKey {
+    set_id: u16,
+    body_id: u16,
+}
+
+// ExtraSkeletonTable
+pub struct Est {
+    data: Vec<u8>,
+}
+
+impl Est {
+    pub const HEAD_PATH: &'static str = "chara/xls/charadb/extra_met.est";
+    pub const BODY_PATH: &'static str = "chara/xls/charadb/extra_top.est";
+    pub const HAIR_PATH: &'static str = "chara/xls/charadb/hairskeletontemplate.est";
+    pub const FACE_PATH: &'static str = "chara/xls/charadb/faceskeletontemplate.est";
+
+    pub async fn new<T: AsRef<str>>(package: &dyn Package, path: T) -> Result<Self> {
+        let data = package.read_file(path.as_ref()).await?;
+
+        Ok(Self { data })
+    }
+
+    // (set_id, body_id, skeleton_id) of every entry
+    pub fn entries(&self) -> impl Iterator<Item = (u16, u16, u16)> + '_ {
+        let (keys, values) = self.keys_and_values();
+
+        keys.iter().zip(values.iter()).map(|(key, &value)| (key.set_id, key.body_id, value))
+    }
+
+    // skeleton id of chara/human/c{body_id}/skeleton/{met,top,hair,face}/{m,t,h,f}{skeleton_id}
+    pub fn get(&self, set_id: u16, body_id: u16) -> Option<u16> {
+        let (keys, values) = self.keys_and_values();
+
+        let index = keys.iter().position(|x| x.set_id == set_id && x.body_id == body_id)?;
+
+        Some(values[index])
+    }
+
+    fn keys_and_values(&self) -> (&[ExtraSkeletonTableKey], &[u16]) {
+        let header = cast::<ExtraSkeletonTableHeader>(&self.data);
+        let count = header.count as usize;
+
+        let keys_offset = size_of::<ExtraSkeletonTableHeader>();
+        let keys = &cast_array::<ExtraSkeletonTableKey>(&self.data[keys_offset..])[..count];
+        let values = &cast_array::<u16>(&self.data[keys_offset + count * size_of::<ExtraSkeletonTableKey>()..])[..count];
+
+        (keys, values)
+    }
+}
@@ -6,7 +6,7 @@ mod exh;
 mod exl;
 
 pub use definition::ExRowType;
-pub use ex_row::ExRow;
+pub use ex_row::{ExRow, ExRowItem};
 pub use exl::ExList;
 
 use core::mem::size_of;
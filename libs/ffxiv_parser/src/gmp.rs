@@ -0,0 +1,47 @@
+use alloc::vec::Vec;
+
+use sqpack::{Package, Result};
+
+use crate::eqp::read_block_entry;
+
+#[derive(Clone, Copy, Default)]
+pub struct GmpEntry {
+    pub enabled: bool,
+    pub animated: bool,
+    pub rotation_a: u16,
+    pub rotation_b: u16,
+    pub rotation_c: u16,
+    pub unknown_a: u8,
+    pub unknown_b: u8,
+}
+
+impl GmpEntry {
+    pub fn from_raw(raw: u64) -> Self {
+        Self {
+            enabled: raw & 1 != 0,
+            animated: raw & 2 != 0,
+            rotation_a: ((raw >> 2) & 0x3ff) as u16,
+            rotation_b: ((raw >> 12) & 0x3ff) as u16,
+            rotation_c: ((raw >> 22) & 0x3ff) as u16,
+            unknown_a: ((raw >> 32) & 0xf) as u8,
+            unknown_b: ((raw >> 36) & 0xf) as u8,
+        }
+    }
+}
+
+// GimmickParameter
+pub struct Gmp {
+    data: Vec<u8>,
+}
+
+impl Gmp {
+    pub async fn new(package: &dyn Package) -> Result<Self> {
+        let data = package.read_file("chara/xls/equipmentparameter/gimmickparameter.gmp").await?;
+
+        Ok(Self { data })
+    }
+
+    pub fn get(&self, set_id: u16) -> GmpEntry {
+        read_block_entry(&self.data, set_id).map(GmpEntry::from_raw).unwrap_or_default()
+    }
+}
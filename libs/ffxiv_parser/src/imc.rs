@@ -0,0 +1,73 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use sqpack::{Package, Result};
+use util::{cast, cast_array};
+
+#[repr(C)]
+struct ImageChangeHeader {
+    variant_count: u16,
+    part_mask: u16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ImcEntry {
+    pub material_id: u8,
+    pub decal_id: u8,
+    attribute_and_sound: u16,
+    pub vfx_id: u8,
+    pub material_animation_id: u8,
+}
+
+impl ImcEntry {
+    pub fn attribute_mask(&self) -> u16 {
+        self.attribute_and_sound & 0x3ff
+    }
+
+    pub fn sound_id(&self) -> u8 {
+        (self.attribute_and_sound >> 10) as u8
+    }
+}
+
+// ImageChange
+pub struct Imc {
+    data: Vec<u8>,
+}
+
+impl Imc {
+    pub async fn new<T: AsRef<str>>(package: &dyn Package, path: T) -> Result<Self> {
+        let data = package.read_file(path.as_ref()).await?;
+
+        Ok(Self { data })
+    }
+
+    pub fn variant_count(&self) -> u16 {
+        let header = cast::<ImageChangeHeader>(&self.data);
+
+        header.variant_count
+    }
+
+    pub fn has_part(&self, part_index: usize) -> bool {
+        let header = cast::<ImageChangeHeader>(&self.data);
+
+        part_index < 16 && header.part_mask & (1 << part_index) != 0
+    }
+
+    // variant 0 is default variant. unknown variants fall back to it. None if the part is not in this file.
+    pub fn get(&self, variant_id: u16, part_index: usize) -> Option<&ImcEntry> {
+        if !self.has_part(part_index) {
+            return None;
+        }
+
+        let header = cast::<ImageChangeHeader>(&self.data);
+        let part_count = header.part_mask.count_ones() as usize;
+
+        let variant_id = if variant_id > header.variant_count { 0 } else { variant_id as usize };
+        let part_offset = (header.part_mask & ((1 << part_index) - 1)).count_ones() as usize;
+
+        let entries = cast_array::<ImcEntry>(&self.data[size_of::<ImageChangeHeader>()..]);
+
+        entries.get(variant_id * part_count + part_offset)
+    }
+}
@@ -5,18 +5,27 @@ compile_error!("Not supported on big endian");
 
 extern crate alloc;
 
+mod cmp;
 mod eqdp;
+mod eqp;
+mod est;
 mod ex;
 mod ffxiv_string;
+mod gmp;
+mod imc;
 mod lgb;
 mod lvb;
 mod mdl;
 mod mtrl;
 mod pap;
 mod pbd;
+mod pcb;
+mod phyb;
 mod sklb;
 mod stm;
+mod tera;
 mod tex;
+mod tmb;
 
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -52,15 +61,24 @@ impl Language {
     }
 }
 
+pub use cmp::{Cmp, RacialScaling};
 pub use eqdp::Eqdp;
-pub use ex::{Ex, ExList, ExRow, ExRowType};
+pub use eqp::{Eqp, EqpEntry};
+pub use est::Est;
+pub use ex::{Ex, ExList, ExRow, ExRowItem, ExRowType};
 pub use ffxiv_string::FfxivString;
+pub use gmp::{Gmp, GmpEntry};
+pub use imc::{Imc, ImcEntry};
 pub use lgb::{LayerGroupResourceItem, Lgb};
-pub use lvb::Lvb;
+pub use lvb::{Lvb, LvbEnvironment, LvbFestival, LvbLayerFilter};
 pub use mdl::{BufferItemChunk, BufferItemType, BufferItemUsage, Mdl, MdlMesh};
-pub use mtrl::{Mtrl, MtrlParameterType};
-pub use pap::Pap;
+pub use mtrl::{Mtrl, MtrlConstant, MtrlParameterType};
+pub use pap::{Pap, PapAnimation};
 pub use pbd::Pbd;
+pub use pcb::{Pcb, PcbNode, PcbTriangle, PcbTriangleSoup};
+pub use phyb::{Phyb, PhybChain, PhybCollisionObject, PhybCollisionShape, PhybNode, PhybSimulator, PhybSpring};
 pub use sklb::Sklb;
 pub use stm::Stm;
+pub use tera::{Tera, TerrainPlate};
 pub use tex::{Tex, TextureType};
+pub use tmb::{Tmb, TmbActor, TmbEntry, TmbEvent, TmbEventKind, TmbTrack};
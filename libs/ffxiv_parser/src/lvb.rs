@@ -2,7 +2,7 @@ use alloc::{borrow::ToOwned, string::String, vec::Vec};
 use core::mem::size_of;
 
 use sqpack::{Package, Result};
-use util::{SliceByteOrderExt, StrExt, cast};
+use util::{SliceByteOrderExt, StrExt, cast, cast_array};
 
 #[repr(C)]
 struct LvbHeader {
@@ -26,9 +26,83 @@ struct LvbEntries {
     pub entry5_offset: u32,
 }
 
+// entry1
+#[repr(C)]
+struct LvbSceneSettings {
+    _have_layer_groups: u32,
+    terrain_path_offset: u32,
+    sky_visibility_path_offset: u32,
+    lcb_path_offset: u32,
+    _lhd_path_offset: u32,
+    _unk1: u32,
+    _unk2: u32,
+}
+
+// entry2
+#[repr(C)]
+struct LvbLayerFilterHeader {
+    filters_offset: u32,
+    filter_count: u32,
+}
+
+#[repr(C)]
+struct LvbLayerFilterEntry {
+    territory_type_id: u32,
+    _territory_type_kind: u32,
+    layer_ids_offset: u32,
+    layer_id_count: u32,
+}
+
+// entry3
+#[repr(C)]
+struct LvbFestivalHeader {
+    festivals_offset: u32,
+    festival_count: u32,
+}
+
+#[repr(C)]
+struct LvbFestivalEntry {
+    festival_id: u16,
+    phase: u16,
+    layer_ids_offset: u32,
+    layer_id_count: u32,
+}
+
+// entry5
+#[repr(C)]
+struct LvbEnvironmentSettings {
+    environment_set_path_offset: u32,
+    time_of_day: i32,
+    weather_ids_offset: u32,
+    weather_id_count: u32,
+}
+
+pub struct LvbLayerFilter {
+    pub territory_type_id: u32,
+    pub layer_ids: Vec<u32>,
+}
+
+pub struct LvbFestival {
+    pub festival_id: u16,
+    pub phase: u16,
+    pub layer_ids: Vec<u32>,
+}
+
+pub struct LvbEnvironment {
+    pub sky_visibility_path: Option<String>,
+    pub lcb_path: Option<String>,
+    pub environment_set_path: Option<String>,
+    pub time_of_day: Option<u32>, // None if time flows
+    pub weather_ids: Vec<u32>,
+}
+
 // LevelSceneResource
 pub struct Lvb {
     pub lgb_paths: Vec<String>,
+    pub terrain_path: Option<String>,
+    pub layer_filters: Vec<LvbLayerFilter>,
+    pub festivals: Vec<LvbFestival>,
+    pub environment: LvbEnvironment,
 }
 
 impl Lvb {
@@ -36,9 +110,10 @@ impl Lvb {
         let data = package.read_file(path).await?;
 
         let _ = cast::<LvbHeader>(&data);
-        let entries = cast::<LvbEntries>(&data[size_of::<LvbHeader>()..]);
+        let entries_base = size_of::<LvbHeader>();
+        let entries = cast::<LvbEntries>(&data[entries_base..]);
 
-        let lgb_entry_base = size_of::<LvbHeader>() + entries.lgb_entry_offset as usize;
+        let lgb_entry_base = entries_base + entries.lgb_entry_offset as usize;
         let lgb_paths = (0..entries.lgb_entry_count as usize)
             .map(|x| {
                 let offset = lgb_entry_base + x * size_of::<u32>();
@@ -50,6 +125,110 @@ impl Lvb {
             })
             .collect::<Vec<_>>();
 
-        Ok(Self { lgb_paths })
+        let scene_settings_base = entries_base + entries.entry1_offset as usize;
+        let scene_settings = cast::<LvbSceneSettings>(&data[scene_settings_base..]);
+        let terrain_path = Self::read_string(&data, scene_settings_base, scene_settings.terrain_path_offset);
+
+        let layer_filters = if entries.entry2_offset != 0 {
+            Self::parse_layer_filters(&data, entries_base + entries.entry2_offset as usize)
+        } else {
+            Vec::new()
+        };
+
+        let festivals = if entries.entry3_offset != 0 {
+            Self::parse_festivals(&data, entries_base + entries.entry3_offset as usize)
+        } else {
+            Vec::new()
+        };
+
+        let environment = Self::parse_environment(&data, scene_settings_base, scene_settings, entries_base, entries.entry5_offset);
+
+        Ok(Self {
+            lgb_paths,
+            terrain_path,
+            layer_filters,
+            festivals,
+            environment,
+        })
+    }
+
+    fn parse_layer_filters(data: &[u8], base: usize) -> Vec<LvbLayerFilter> {
+        let header = cast::<LvbLayerFilterHeader>(&data[base..]);
+
+        let filters_base = base + header.filters_offset as usize;
+        let filters = &cast_array::<LvbLayerFilterEntry>(&data[filters_base..])[..header.filter_count as usize];
+
+        filters
+            .iter()
+            .map(|x| LvbLayerFilter {
+                territory_type_id: x.territory_type_id,
+                layer_ids: Self::read_u32_array(data, filters_base, x.layer_ids_offset, x.layer_id_count),
+            })
+            .collect()
+    }
+
+    fn parse_festivals(data: &[u8], base: usize) -> Vec<LvbFestival> {
+        let header = cast::<LvbFestivalHeader>(&data[base..]);
+
+        let festivals_base = base + header.festivals_offset as usize;
+        let festivals = &cast_array::<LvbFestivalEntry>(&data[festivals_base..])[..header.festival_count as usize];
+
+        festivals
+            .iter()
+            .map(|x| LvbFestival {
+                festival_id: x.festival_id,
+                phase: x.phase,
+                layer_ids: Self::read_u32_array(data, festivals_base, x.layer_ids_offset, x.layer_id_count),
+            })
+            .collect()
+    }
+
+    fn parse_environment(
+        data: &[u8],
+        scene_settings_base: usize,
+        scene_settings: &LvbSceneSettings,
+        entries_base: usize,
+        environment_offset: u32,
+    ) -> LvbEnvironment {
+        let sky_visibility_path = Self::read_string(data, scene_settings_base, scene_settings.sky_visibility_path_offset);
+        let lcb_path = Self::read_string(data, scene_settings_base, scene_settings.lcb_path_offset);
+
+        let (environment_set_path, time_of_day, weather_ids) = if environment_offset != 0 {
+            let base = entries_base + environment_offset as usize;
+            let environment_settings = cast::<LvbEnvironmentSettings>(&data[base..]);
+
+            (
+                Self::read_string(data, base, environment_settings.environment_set_path_offset),
+                u32::try_from(environment_settings.time_of_day).ok(),
+                Self::read_u32_array(data, base, environment_settings.weather_ids_offset, environment_settings.weather_id_count),
+            )
+        } else {
+            (None, None, Vec::new())
+        };
+
+        LvbEnvironment {
+            sky_visibility_path,
+            lcb_path,
+            environment_set_path,
+            time_of_day,
+            weather_ids,
+        }
+    }
+
+    fn read_string(data: &[u8], base: usize, offset: u32) -> Option<String> {
+        if offset == 0 {
+            return None;
+        }
+
+        let result = str::from_null_terminated_utf8(&data[base + offset as usize..]).unwrap();
+        if result.is_empty() { None } else { Some(result.to_owned()) }
+    }
+
+    fn read_u32_array(data: &[u8], base: usize, offset: u32, count: u32) -> Vec<u32> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        cast_array::<u32>(&data[base + offset as usize..])[..count as usize].to_vec()
     }
 }
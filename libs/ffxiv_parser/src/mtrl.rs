@@ -17,6 +17,7 @@ struct MtrlHeader {
     unk_size: u8,
 }
 
+// crc32 of sampler names
 #[repr(u32)]
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub enum MtrlParameterType {
@@ -25,21 +26,64 @@ pub enum MtrlParameterType {
     Diffuse = 0x1153_06BE,
     Specular = 0x2B99_E025,
     Catchlight = 0xFEA0_F3D2,
+
+    // background shaders have two layers
+    ColorMap0 = 0x1E6F_EF9C,
+    ColorMap1 = 0x6968_DF0A,
+    NormalMap0 = 0xAAB4_D9E9,
+    NormalMap1 = 0xDDB3_E97F,
+    SpecularMap0 = 0x1BBC_2F12,
+    SpecularMap1 = 0x6CBB_1F84,
+
+    Unknown = 0,
+}
+
+impl MtrlParameterType {
+    pub fn from_raw(raw: u32) -> Self {
+        match raw {
+            0x0C5E_C1F1 => MtrlParameterType::Normal,
+            0x8A4E_82B6 => MtrlParameterType::Mask,
+            0x1153_06BE => MtrlParameterType::Diffuse,
+            0x2B99_E025 => MtrlParameterType::Specular,
+            0xFEA0_F3D2 => MtrlParameterType::Catchlight,
+            0x1E6F_EF9C => MtrlParameterType::ColorMap0,
+            0x6968_DF0A => MtrlParameterType::ColorMap1,
+            0xAAB4_D9E9 => MtrlParameterType::NormalMap0,
+            0xDDB3_E97F => MtrlParameterType::NormalMap1,
+            0x1BBC_2F12 => MtrlParameterType::SpecularMap0,
+            0x6CBB_1F84 => MtrlParameterType::SpecularMap1,
+            _ => MtrlParameterType::Unknown,
+        }
+    }
 }
 
 #[repr(C)]
 pub struct MtrlParameter {
-    pub parameter_type: MtrlParameterType,
+    parameter_type: u32,
     _unk1: u16,
     _unk2: u16,
     pub texture_index: u32,
 }
 
+impl MtrlParameter {
+    pub fn parameter_type(&self) -> MtrlParameterType {
+        MtrlParameterType::from_raw(self.parameter_type)
+    }
+}
+
+// shader constant, values are at offset in constant data
+#[repr(C)]
+pub struct MtrlConstant {
+    pub id: u32,
+    offset: u16,
+    size: u16,
+}
+
 #[repr(C)]
 struct MtrlMetadataHeader {
-    data_size: u16,
+    constant_data_size: u16,
     unk_struct1_count: u16,
-    unk_struct2_count: u16,
+    constant_count: u16,
     parameter_count: u16,
     _unk1: u16,
     _unk2: u16,
@@ -50,7 +94,9 @@ pub struct Mtrl {
     strings_offset: usize,
     color_table_offset: usize,
     metadata_header_offset: usize,
+    constants_offset: usize,
     parameters_offset: usize,
+    constant_data_offset: usize,
 }
 
 impl Mtrl {
@@ -65,17 +111,18 @@ impl Mtrl {
         let color_table_offset = strings_offset + header.strings_size as usize;
         let metadata_header_offset = color_table_offset + header.color_table_size as usize + header.unk_size as usize;
         let metadata_header = cast::<MtrlMetadataHeader>(&data[metadata_header_offset..]);
-        let parameters_offset = metadata_header_offset
-            + size_of::<MtrlMetadataHeader>()
-            + 8 * metadata_header.unk_struct1_count as usize
-            + 8 * metadata_header.unk_struct2_count as usize;
+        let constants_offset = metadata_header_offset + size_of::<MtrlMetadataHeader>() + 8 * metadata_header.unk_struct1_count as usize;
+        let parameters_offset = constants_offset + size_of::<MtrlConstant>() * metadata_header.constant_count as usize;
+        let constant_data_offset = parameters_offset + size_of::<MtrlParameter>() * metadata_header.parameter_count as usize;
 
         Ok(Self {
             data,
             strings_offset,
             color_table_offset,
             metadata_header_offset,
+            constants_offset,
             parameters_offset,
+            constant_data_offset,
         })
     }
 
@@ -108,6 +155,29 @@ impl Mtrl {
         &parameters[..metadata_header.parameter_count as usize]
     }
 
+    pub fn constants(&self) -> &[MtrlConstant] {
+        let metadata_header = cast::<MtrlMetadataHeader>(&self.data[self.metadata_header_offset..]);
+        let constants = cast_array::<MtrlConstant>(&self.data[self.constants_offset..]);
+
+        &constants[..metadata_header.constant_count as usize]
+    }
+
+    // values of constant by its id (crc32 of name)
+    pub fn constant(&self, id: u32) -> Option<&[f32]> {
+        let metadata_header = cast::<MtrlMetadataHeader>(&self.data[self.metadata_header_offset..]);
+        let constant = self.constants().iter().find(|x| x.id == id)?;
+
+        let start = constant.offset as usize;
+        let end = start + constant.size as usize;
+        if end > metadata_header.constant_data_size as usize {
+            return None;
+        }
+
+        let values = cast_array::<f32>(&self.data[self.constant_data_offset..]);
+
+        Some(&values[start / size_of::<f32>()..end / size_of::<f32>()])
+    }
+
     pub fn color_table(&self) -> &[u8] {
         let header = cast::<MtrlHeader>(&self.data);
         let offset = self.color_table_offset + size_of::<u32>();
@@ -1,11 +1,14 @@
 use alloc::vec::Vec;
+use core::str;
 
 use sqpack::{Package, Result};
-use util::cast;
+use util::{cast, cast_array, round_up};
+
+use crate::tmb::Tmb;
 
 #[repr(C, packed(1))]
 struct PartialAnimationPackHeader {
-    signature: u32,
+    _signature: u32,
     _unk1: u16,
     _unk2: u16,
     animation_count: u16,
@@ -16,6 +19,33 @@ struct PartialAnimationPackHeader {
     footer_offset: u32,
 }
 
+#[repr(C)]
+struct TimelineHeader {
+    _signature: u32,
+    size: u32,
+}
+
+#[repr(C)]
+pub struct PapAnimation {
+    name: [u8; 32],
+    pub animation_type: u16,
+    pub havok_index: u16,
+    is_face: u8,
+    _padding: [u8; 3],
+}
+
+impl PapAnimation {
+    pub fn name(&self) -> Option<&str> {
+        let end = self.name.iter().position(|&x| x == b'\0').unwrap_or(self.name.len());
+
+        str::from_utf8(&self.name[..end]).ok()
+    }
+
+    pub fn is_face(&self) -> bool {
+        self.is_face != 0
+    }
+}
+
 // PartialAnimationPack
 pub struct Pap {
     data: Vec<u8>,
@@ -28,8 +58,52 @@ impl Pap {
         Ok(Self { data })
     }
 
+    pub fn body_id(&self) -> u16 {
+        let header = cast::<PartialAnimationPackHeader>(&self.data);
+
+        header.body_id
+    }
+
+    pub fn animations(&self) -> &[PapAnimation] {
+        let header = cast::<PartialAnimationPackHeader>(&self.data);
+
+        &cast_array::<PapAnimation>(&self.data[header.header_size as usize..])[..header.animation_count as usize]
+    }
+
+    pub fn find_animation(&self, name: &str) -> Option<&PapAnimation> {
+        self.animations().iter().find(|x| x.name() == Some(name))
+    }
+
     pub fn hkx_data(&self) -> &[u8] {
         let header = cast::<PartialAnimationPackHeader>(&self.data);
-        &self.data[header.hkx_offset as usize..]
+        &self.data[header.hkx_offset as usize..header.footer_offset as usize]
+    }
+
+    // footer contains one tmb per animation, each aligned to 4 bytes
+    pub fn timeline_data(&self, animation_index: usize) -> Option<&[u8]> {
+        let header = cast::<PartialAnimationPackHeader>(&self.data);
+        if animation_index >= header.animation_count as usize {
+            return None;
+        }
+
+        let mut offset = header.footer_offset as usize;
+        for _ in 0..animation_index {
+            offset = round_up(offset + self.timeline_size(offset)?, 4);
+        }
+
+        self.data.get(offset..offset + self.timeline_size(offset)?)
+    }
+
+    pub fn timeline(&self, animation_index: usize) -> Option<Tmb> {
+        Some(Tmb::from_raw(self.timeline_data(animation_index)?.to_vec()))
+    }
+
+    fn timeline_size(&self, offset: usize) -> Option<usize> {
+        let data = self.data.get(offset..)?;
+        if data.len() < size_of::<TimelineHeader>() {
+            return None;
+        }
+
+        Some(cast::<TimelineHeader>(data).size as usize)
     }
 }
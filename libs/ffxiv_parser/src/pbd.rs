@@ -22,7 +22,7 @@ struct PreBoneDeformerHeader {
 
 #[repr(C)]
 struct PreBoneDeformerLink {
-    next_index: i16,
+    parent_index: i16,
     _unk1: u16,
     _unk2: u16,
     next_item_index: u16,
@@ -40,13 +40,31 @@ impl Pbd {
         Ok(Self { data })
     }
 
+    pub fn body_ids(&self) -> Vec<u16> {
+        self.items().iter().map(|x| x.body_id).collect()
+    }
+
+    // deformers form a tree rooted at midlander male, parent is the body that models fall back to
+    pub fn parent_body_id(&self, body_id: u16) -> Option<u16> {
+        let items = self.items();
+        let item = items.iter().find(|x| x.body_id == body_id)?;
+
+        let link = &self.links()[item.link_index as usize];
+        if link.parent_index == -1 {
+            return None;
+        }
+
+        let parent = &self.links()[link.parent_index as usize];
+        Some(items[parent.next_item_index as usize].body_id)
+    }
+
     pub fn get_deform_matrices(&self, from_id: u16, to_id: u16) -> HashMap<String, Mat4> {
         if from_id == to_id {
             return HashMap::new();
         }
 
-        let header = cast::<PreBoneDeformerHeader>(&self.data);
-        let items = &cast_array::<PreBoneDeformerItem>(&self.data[size_of::<PreBoneDeformerHeader>()..])[..header.count as usize];
+        let items = self.items();
+        let links = self.links();
 
         let item = items.iter().find(|x| x.body_id == from_id);
         if item.is_none() {
@@ -54,13 +72,9 @@ impl Pbd {
         }
         let mut item = item.unwrap();
 
-        let base_offset = size_of::<PreBoneDeformerHeader>();
-        let link_base_offset = base_offset + size_of::<PreBoneDeformerItem>() * header.count as usize;
-        let links = cast_array::<PreBoneDeformerLink>(&self.data[link_base_offset..]);
-
         let mut next = &links[item.link_index as usize];
 
-        if next.next_index == -1 {
+        if next.parent_index == -1 {
             return HashMap::new();
         }
 
@@ -87,7 +101,7 @@ impl Pbd {
                 ]);
             }
 
-            next = &links[next.next_index as usize];
+            next = &links[next.parent_index as usize];
             item = &items[next.next_item_index as usize];
 
             if item.body_id == to_id {
@@ -97,4 +111,17 @@ impl Pbd {
 
         result
     }
+
+    fn items(&self) -> &[PreBoneDeformerItem] {
+        let header = cast::<PreBoneDeformerHeader>(&self.data);
+
+        &cast_array::<PreBoneDeformerItem>(&self.data[size_of::<PreBoneDeformerHeader>()..])[..header.count as usize]
+    }
+
+    fn links(&self) -> &[PreBoneDeformerLink] {
+        let header = cast::<PreBoneDeformerHeader>(&self.data);
+        let link_base_offset = size_of::<PreBoneDeformerHeader>() + size_of::<PreBoneDeformerItem>() * header.count as usize;
+
+        cast_array::<PreBoneDeformerLink>(&self.data[link_base_offset..])
+    }
 }
@@ -0,0 +1,163 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt::Write, mem::size_of};
+
+use glam::Vec3;
+
+use sqpack::{Package, Result};
+use util::{cast, cast_array};
+
+#[repr(C)]
+struct PcbHeader {
+    _unk1: u32,
+    _version: u32,
+    _total_nodes: u32,
+    _total_triangles: u32,
+    _padding: u64,
+}
+
+#[repr(C)]
+struct PcbNodeHeader {
+    _unk1: u32,
+    _unk2: u32,
+    child1_offset: u32,
+    child2_offset: u32,
+    bbox_min: [f32; 3],
+    bbox_max: [f32; 3],
+    compressed_vertex_count: u16,
+    triangle_count: u16,
+    vertex_count: u16,
+    _padding: u16,
+}
+
+#[repr(C, packed(1))]
+struct PcbRawTriangle {
+    indices: [u8; 3],
+    _unk: u8,
+    material: u64,
+}
+
+#[derive(Clone, Copy)]
+pub struct PcbTriangle {
+    pub indices: [u8; 3],
+    pub material: u64,
+}
+
+pub struct PcbNode {
+    pub bbox_min: Vec3,
+    pub bbox_max: Vec3,
+    pub vertices: Vec<Vec3>,
+    pub triangles: Vec<PcbTriangle>,
+    pub children: Vec<PcbNode>,
+}
+
+impl PcbNode {
+    fn parse(data: &[u8]) -> Self {
+        let header = cast::<PcbNodeHeader>(data);
+        let bbox_min = Vec3::from(header.bbox_min);
+        let bbox_max = Vec3::from(header.bbox_max);
+
+        let mut cursor = size_of::<PcbNodeHeader>();
+
+        let raw_vertices = &cast_array::<[f32; 3]>(&data[cursor..])[..header.vertex_count as usize];
+        cursor += size_of::<[f32; 3]>() * header.vertex_count as usize;
+
+        let raw_compressed_vertices = &cast_array::<[u16; 3]>(&data[cursor..])[..header.compressed_vertex_count as usize];
+        cursor += size_of::<[u16; 3]>() * header.compressed_vertex_count as usize;
+
+        // compressed vertices are quantized in bounding box
+        let vertices = raw_vertices
+            .iter()
+            .map(|&x| Vec3::from(x))
+            .chain(raw_compressed_vertices.iter().map(|x| {
+                let normalized = Vec3::new(x[0] as f32, x[1] as f32, x[2] as f32) / 65535.;
+
+                bbox_min + normalized * (bbox_max - bbox_min)
+            }))
+            .collect::<Vec<_>>();
+
+        let raw_triangles = &cast_array::<PcbRawTriangle>(&data[cursor..])[..header.triangle_count as usize];
+        let triangles = raw_triangles
+            .iter()
+            .map(|x| PcbTriangle {
+                indices: x.indices,
+                material: x.material,
+            })
+            .collect::<Vec<_>>();
+
+        let children = [header.child1_offset, header.child2_offset]
+            .iter()
+            .filter(|&&x| x != 0)
+            .map(|&x| Self::parse(&data[x as usize..]))
+            .collect::<Vec<_>>();
+
+        Self {
+            bbox_min,
+            bbox_max,
+            vertices,
+            triangles,
+            children,
+        }
+    }
+}
+
+pub struct PcbTriangleSoup {
+    pub vertices: Vec<Vec3>,
+    pub indices: Vec<u32>,
+    pub materials: Vec<u64>,
+}
+
+impl PcbTriangleSoup {
+    pub fn to_obj(&self) -> String {
+        let mut result = String::new();
+
+        for vertex in &self.vertices {
+            writeln!(result, "v {} {} {}", vertex.x, vertex.y, vertex.z).unwrap();
+        }
+
+        // obj indices are 1-based
+        for triangle in self.indices.chunks(3) {
+            writeln!(result, "f {} {} {}", triangle[0] + 1, triangle[1] + 1, triangle[2] + 1).unwrap();
+        }
+
+        result
+    }
+}
+
+// Collision
+pub struct Pcb {
+    pub root: PcbNode,
+}
+
+impl Pcb {
+    pub async fn new<T: AsRef<str>>(package: &dyn Package, path: T) -> Result<Self> {
+        let data = package.read_file(path.as_ref()).await?;
+
+        let _ = cast::<PcbHeader>(&data);
+        let root = PcbNode::parse(&data[size_of::<PcbHeader>()..]);
+
+        Ok(Self { root })
+    }
+
+    pub fn triangle_soup(&self) -> PcbTriangleSoup {
+        let mut result = PcbTriangleSoup {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            materials: Vec::new(),
+        };
+
+        let mut nodes = vec![&self.root];
+        while let Some(node) = nodes.pop() {
+            let base = result.vertices.len() as u32;
+
+            result.vertices.extend(node.vertices.iter().cloned());
+            for triangle in &node.triangles {
+                result.indices.extend(triangle.indices.iter().map(|&x| base + x as u32));
+                result.materials.push(triangle.material);
+            }
+
+            nodes.extend(node.children.iter());
+        }
+
+        result
+    }
+}
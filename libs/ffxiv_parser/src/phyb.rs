@@ -0,0 +1,365 @@
+use alloc::{string::String, vec::Vec};
+use core::mem::size_of;
+
+use sqpack::{Package, Result};
+use util::{cast, cast_array};
+
+#[repr(C)]
+struct CollisionHeader {
+    capsule_count: u8,
+    ellipsoid_count: u8,
+    normal_plane_count: u8,
+    three_point_plane_count: u8,
+    sphere_count: u8,
+    _padding: [u8; 3],
+}
+
+#[repr(C)]
+struct RawCapsule {
+    name: [u8; 32],
+    bone_name_1: [u8; 32],
+    bone_name_2: [u8; 32],
+    bone_offset_1: [f32; 3],
+    bone_offset_2: [f32; 3],
+    radius: f32,
+}
+
+#[repr(C)]
+struct RawEllipsoid {
+    name: [u8; 32],
+    bone_name: [u8; 32],
+    offsets: [[f32; 3]; 4],
+    radius: f32,
+}
+
+#[repr(C)]
+struct RawNormalPlane {
+    name: [u8; 32],
+    bone_name: [u8; 32],
+    bone_offset: [f32; 3],
+    normal: [f32; 3],
+    thickness: f32,
+}
+
+#[repr(C)]
+struct RawThreePointPlane {
+    name: [u8; 32],
+    bone_names: [[u8; 32]; 3],
+    bone_offsets: [[f32; 3]; 3],
+    thickness: f32,
+}
+
+#[repr(C)]
+struct RawSphere {
+    name: [u8; 32],
+    bone_name: [u8; 32],
+    bone_offset: [f32; 3],
+    radius: f32,
+}
+
+#[repr(C)]
+struct SimulatorHeader {
+    _collision_count: u8,
+    _collision_connector_count: u8,
+    chain_count: u8,
+    _connector_count: u8,
+    _attract_count: u8,
+    _pin_count: u8,
+    spring_count: u8,
+    _post_alignment_count: u8,
+    gravity: [f32; 3],
+    wind: [f32; 3],
+    constraint_loop: u16,
+    collision_loop: u16,
+    _flags: u8,
+    _group: u8,
+    _padding: u16,
+    _collision_offset: u32,
+    _collision_connector_offset: u32,
+    chain_offset: u32,
+    _connector_offset: u32,
+    _attract_offset: u32,
+    _pin_offset: u32,
+    spring_offset: u32,
+    _post_alignment_offset: u32,
+}
+
+#[repr(C)]
+struct RawChain {
+    damping: f32,
+    max_speed: f32,
+    friction: f32,
+    collision_damping: f32,
+    repulsion_strength: f32,
+    last_bone_offset: [f32; 3],
+    chain_type: u32,
+    _collision_offset: u32,
+    node_offset: u32,
+    _collision_count: u16,
+    node_count: u16,
+}
+
+#[repr(C)]
+struct RawNode {
+    bone_name: [u8; 32],
+    cone_max_angle: f32,
+    cone_axis_offset: [f32; 3],
+    constraint_plane_normal: [f32; 3],
+    collision_radius: f32,
+    attract_type: u32,
+}
+
+#[repr(C)]
+struct RawSpring {
+    chain_id_1: u16,
+    chain_id_2: u16,
+    node_id_1: u16,
+    node_id_2: u16,
+    stretch_stiffness: f32,
+    compress_stiffness: f32,
+}
+
+pub enum PhybCollisionShape {
+    Capsule {
+        bone_names: [String; 2],
+        bone_offsets: [[f32; 3]; 2],
+        radius: f32,
+    },
+    // offsets are bone offset followed by three axes
+    Ellipsoid {
+        bone_name: String,
+        offsets: [[f32; 3]; 4],
+        radius: f32,
+    },
+    NormalPlane {
+        bone_name: String,
+        bone_offset: [f32; 3],
+        normal: [f32; 3],
+        thickness: f32,
+    },
+    ThreePointPlane {
+        bone_names: [String; 3],
+        bone_offsets: [[f32; 3]; 3],
+        thickness: f32,
+    },
+    Sphere {
+        bone_name: String,
+        bone_offset: [f32; 3],
+        radius: f32,
+    },
+}
+
+pub struct PhybCollisionObject {
+    pub name: String,
+    pub shape: PhybCollisionShape,
+}
+
+pub struct PhybNode {
+    pub bone_name: String,
+    pub cone_max_angle: f32,
+    pub cone_axis_offset: [f32; 3],
+    pub constraint_plane_normal: [f32; 3],
+    pub collision_radius: f32,
+    pub attract_type: u32,
+}
+
+pub struct PhybChain {
+    pub damping: f32,
+    pub max_speed: f32,
+    pub friction: f32,
+    pub collision_damping: f32,
+    pub repulsion_strength: f32,
+    pub last_bone_offset: [f32; 3],
+    pub chain_type: u32,
+    pub nodes: Vec<PhybNode>,
+}
+
+pub struct PhybSpring {
+    pub chain_ids: [u16; 2],
+    pub node_ids: [u16; 2],
+    pub stretch_stiffness: f32,
+    pub compress_stiffness: f32,
+}
+
+pub struct PhybSimulator {
+    pub gravity: [f32; 3],
+    pub wind: [f32; 3],
+    pub constraint_loop: u16,
+    pub collision_loop: u16,
+    pub chains: Vec<PhybChain>,
+    pub springs: Vec<PhybSpring>,
+}
+
+// PhysicsBone
+pub struct Phyb {
+    data: Vec<u8>,
+    collision_offset: u32,
+    simulation_offset: u32,
+}
+
+impl Phyb {
+    pub async fn new(package: &dyn Package, path: &str) -> Result<Self> {
+        let data = package.read_file(path).await?;
+
+        // version 0 doesn't have data type field
+        let version = *cast::<u32>(&data);
+        let offsets_base = if version == 0 { size_of::<u32>() } else { size_of::<u32>() * 2 };
+        let offsets = cast_array::<u32>(&data[offsets_base..]);
+
+        let collision_offset = offsets[0];
+        let simulation_offset = offsets[1];
+
+        Ok(Self {
+            data,
+            collision_offset,
+            simulation_offset,
+        })
+    }
+
+    pub fn collision_objects(&self) -> Vec<PhybCollisionObject> {
+        let base = self.collision_offset as usize;
+        if base == 0 || base >= self.data.len() {
+            return Vec::new();
+        }
+
+        let header = cast::<CollisionHeader>(&self.data[base..]);
+
+        // shapes are stored in order of capsules, ellipsoids, normal planes, three point planes and spheres
+        let capsules_offset = base + size_of::<CollisionHeader>();
+        let ellipsoids_offset = capsules_offset + size_of::<RawCapsule>() * header.capsule_count as usize;
+        let normal_planes_offset = ellipsoids_offset + size_of::<RawEllipsoid>() * header.ellipsoid_count as usize;
+        let three_point_planes_offset = normal_planes_offset + size_of::<RawNormalPlane>() * header.normal_plane_count as usize;
+        let spheres_offset = three_point_planes_offset + size_of::<RawThreePointPlane>() * header.three_point_plane_count as usize;
+
+        let capsules = &cast_array::<RawCapsule>(&self.data[capsules_offset..])[..header.capsule_count as usize];
+        let ellipsoids = &cast_array::<RawEllipsoid>(&self.data[ellipsoids_offset..])[..header.ellipsoid_count as usize];
+        let normal_planes = &cast_array::<RawNormalPlane>(&self.data[normal_planes_offset..])[..header.normal_plane_count as usize];
+        let three_point_planes =
+            &cast_array::<RawThreePointPlane>(&self.data[three_point_planes_offset..])[..header.three_point_plane_count as usize];
+        let spheres = &cast_array::<RawSphere>(&self.data[spheres_offset..])[..header.sphere_count as usize];
+
+        let capsules = capsules.iter().map(|x| PhybCollisionObject {
+            name: Self::to_string(&x.name),
+            shape: PhybCollisionShape::Capsule {
+                bone_names: [Self::to_string(&x.bone_name_1), Self::to_string(&x.bone_name_2)],
+                bone_offsets: [x.bone_offset_1, x.bone_offset_2],
+                radius: x.radius,
+            },
+        });
+        let ellipsoids = ellipsoids.iter().map(|x| PhybCollisionObject {
+            name: Self::to_string(&x.name),
+            shape: PhybCollisionShape::Ellipsoid {
+                bone_name: Self::to_string(&x.bone_name),
+                offsets: x.offsets,
+                radius: x.radius,
+            },
+        });
+        let normal_planes = normal_planes.iter().map(|x| PhybCollisionObject {
+            name: Self::to_string(&x.name),
+            shape: PhybCollisionShape::NormalPlane {
+                bone_name: Self::to_string(&x.bone_name),
+                bone_offset: x.bone_offset,
+                normal: x.normal,
+                thickness: x.thickness,
+            },
+        });
+        let three_point_planes = three_point_planes.iter().map(|x| PhybCollisionObject {
+            name: Self::to_string(&x.name),
+            shape: PhybCollisionShape::ThreePointPlane {
+                bone_names: x.bone_names.each_ref().map(|x| Self::to_string(x)),
+                bone_offsets: x.bone_offsets,
+                thickness: x.thickness,
+            },
+        });
+        let spheres = spheres.iter().map(|x| PhybCollisionObject {
+            name: Self::to_string(&x.name),
+            shape: PhybCollisionShape::Sphere {
+                bone_name: Self::to_string(&x.bone_name),
+                bone_offset: x.bone_offset,
+                radius: x.radius,
+            },
+        });
+
+        capsules
+            .chain(ellipsoids)
+            .chain(normal_planes)
+            .chain(three_point_planes)
+            .chain(spheres)
+            .collect()
+    }
+
+    pub fn simulators(&self) -> Vec<PhybSimulator> {
+        let base = self.simulation_offset as usize;
+        if base == 0 || base >= self.data.len() {
+            return Vec::new();
+        }
+
+        let simulator_count = *cast::<u32>(&self.data[base..]) as usize;
+        let headers = &cast_array::<SimulatorHeader>(&self.data[base + size_of::<u32>()..])[..simulator_count];
+
+        // offsets are relative to the end of simulator count
+        let offset_base = base + size_of::<u32>();
+
+        headers
+            .iter()
+            .map(|header| {
+                let chains = &cast_array::<RawChain>(&self.data[offset_base + header.chain_offset as usize..])[..header.chain_count as usize];
+                let springs = &cast_array::<RawSpring>(&self.data[offset_base + header.spring_offset as usize..])[..header.spring_count as usize];
+
+                let chains = chains
+                    .iter()
+                    .map(|chain| {
+                        let nodes = &cast_array::<RawNode>(&self.data[offset_base + chain.node_offset as usize..])[..chain.node_count as usize];
+
+                        PhybChain {
+                            damping: chain.damping,
+                            max_speed: chain.max_speed,
+                            friction: chain.friction,
+                            collision_damping: chain.collision_damping,
+                            repulsion_strength: chain.repulsion_strength,
+                            last_bone_offset: chain.last_bone_offset,
+                            chain_type: chain.chain_type,
+                            nodes: nodes
+                                .iter()
+                                .map(|node| PhybNode {
+                                    bone_name: Self::to_string(&node.bone_name),
+                                    cone_max_angle: node.cone_max_angle,
+                                    cone_axis_offset: node.cone_axis_offset,
+                                    constraint_plane_normal: node.constraint_plane_normal,
+                                    collision_radius: node.collision_radius,
+                                    attract_type: node.attract_type,
+                                })
+                                .collect(),
+                        }
+                    })
+                    .collect();
+
+                let springs = springs
+                    .iter()
+                    .map(|spring| PhybSpring {
+                        chain_ids: [spring.chain_id_1, spring.chain_id_2],
+                        node_ids: [spring.node_id_1, spring.node_id_2],
+                        stretch_stiffness: spring.stretch_stiffness,
+                        compress_stiffness: spring.compress_stiffness,
+                    })
+                    .collect();
+
+                PhybSimulator {
+                    gravity: header.gravity,
+                    wind: header.wind,
+                    constraint_loop: header.constraint_loop,
+                    collision_loop: header.collision_loop,
+                    chains,
+                    springs,
+                }
+            })
+            .collect()
+    }
+
+    // names are ascii, invalid bytes are replaced rather than failing whole file
+    fn to_string(raw: &[u8]) -> String {
+        let end = raw.iter().position(|&x| x == b'\0').unwrap_or(raw.len());
+
+        String::from_utf8_lossy(&raw[..end]).into_owned()
+    }
+}
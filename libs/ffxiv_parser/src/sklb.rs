@@ -15,10 +15,8 @@ struct SkeletonHeader12 {
     _version: u32,
     _unk_offset: u16,
     hkx_offset: u16,
-    _body_id: u32,
-    _mapper_body_id_1: u32,
-    _mapper_body_id_2: u32,
-    _mapper_body_id_3: u32,
+    body_id: u32,
+    mapper_body_ids: [u32; 3],
 }
 
 #[repr(C)]
@@ -28,16 +26,16 @@ struct SkeletonHeader13 {
     _unk_offset: u32,
     hkx_offset: u32,
     _unk: u32,
-    _body_id: u32,
-    _mapper_body_id_1: u32,
-    _mapper_body_id_2: u32,
-    _mapper_body_id_3: u32,
+    body_id: u32,
+    mapper_body_ids: [u32; 3],
 }
 
 // Skeleton
 pub struct Sklb {
     data: Vec<u8>,
     hkx_offset: u32,
+    body_id: u32,
+    mapper_body_ids: [u32; 3],
 }
 
 impl Sklb {
@@ -45,23 +43,44 @@ impl Sklb {
         let data = package.read_file(path).await?;
 
         let header = cast::<SkeletonHeader>(&data);
-        let hkx_offset;
-        if header.version == 0x3132_3030 {
+        let (hkx_offset, body_id, mapper_body_ids) = if header.version == 0x3132_3030 {
             // '1200'
             let header = cast::<SkeletonHeader12>(&data);
-            hkx_offset = header.hkx_offset as u32;
-        } else if header.version == 0x3133_3030 || header.version == 0x3133_3031 {
-            // '1300' or '1301'
-            let header = cast::<SkeletonHeader13>(&data);
-            hkx_offset = header.hkx_offset;
+            (header.hkx_offset as u32, header.body_id, header.mapper_body_ids)
         } else {
-            panic!()
-        }
+            // '1300' or '1301'. newer versions are assumed to share layout.
+            if header.version != 0x3133_3030 && header.version != 0x3133_3031 {
+                log::warn!("Unknown skeleton version {:08x} in {path}", header.version);
+            }
+
+            let header = cast::<SkeletonHeader13>(&data);
+            (header.hkx_offset, header.body_id, header.mapper_body_ids)
+        };
 
-        Ok(Self { data, hkx_offset })
+        Ok(Self {
+            data,
+            hkx_offset,
+            body_id,
+            mapper_body_ids,
+        })
     }
 
     pub fn hkx_data(&self) -> &[u8] {
         &self.data[self.hkx_offset as usize..]
     }
+
+    pub fn body_id(&self) -> u32 {
+        self.body_id
+    }
+
+    // first mapper id is parent skeleton. unused ids are zero.
+    pub fn mapper_body_ids(&self) -> &[u32] {
+        let count = self.mapper_body_ids.iter().position(|&x| x == 0).unwrap_or(self.mapper_body_ids.len());
+
+        &self.mapper_body_ids[..count]
+    }
+
+    pub fn parent_body_id(&self) -> Option<u32> {
+        self.mapper_body_ids().first().copied()
+    }
 }
@@ -0,0 +1,84 @@
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use core::mem::size_of;
+
+use futures::{FutureExt, future};
+use glam::Vec3;
+
+use sqpack::{Package, Result, SqPackReaderError};
+use util::{cast, cast_array};
+
+use crate::mdl::Mdl;
+
+#[repr(C)]
+struct TerrainHeader {
+    _version: u32,
+    plate_count: u32,
+    plate_size: u32,
+    _clip_distance: f32,
+    _unk1: f32,
+    _padding: [u8; 32],
+}
+
+#[repr(C)]
+struct TerrainPlatePosition {
+    x: i16,
+    y: i16,
+}
+
+pub struct TerrainPlate {
+    pub mdl: Mdl,
+    pub position: Vec3,
+}
+
+// Terrain
+pub struct Tera {
+    data: Vec<u8>,
+    directory: String,
+}
+
+impl Tera {
+    pub async fn new<T: AsRef<str>>(package: &dyn Package, path: T) -> Result<Self> {
+        let path = path.as_ref();
+        let data = package.read_file(path).await?;
+
+        let separator = path.rfind('/').map(|x| x + 1).unwrap_or(0);
+        let directory = path[..separator].to_owned();
+
+        Ok(Self { data, directory })
+    }
+
+    pub fn plate_count(&self) -> usize {
+        let header = cast::<TerrainHeader>(&self.data);
+
+        header.plate_count as usize
+    }
+
+    pub fn plate_size(&self) -> u32 {
+        let header = cast::<TerrainHeader>(&self.data);
+
+        header.plate_size
+    }
+
+    // center of plate in world space
+    pub fn plate_position(&self, index: usize) -> Vec3 {
+        let header = cast::<TerrainHeader>(&self.data);
+        let positions = &cast_array::<TerrainPlatePosition>(&self.data[size_of::<TerrainHeader>()..])[..header.plate_count as usize];
+        let position = &positions[index];
+
+        let plate_size = header.plate_size as f32;
+        Vec3::new(plate_size * (position.x as f32 + 0.5), 0., plate_size * (position.y as f32 + 0.5))
+    }
+
+    pub fn plate_path(&self, index: usize) -> String {
+        format!("{}{:04}.mdl", self.directory, index)
+    }
+
+    pub async fn read_plates(&self, package: &dyn Package) -> Result<Vec<TerrainPlate>> {
+        future::try_join_all((0..self.plate_count()).map(|index| {
+            let position = self.plate_position(index);
+
+            Mdl::new(package, self.plate_path(index)).map(move |mdl| Ok::<_, SqPackReaderError>(TerrainPlate { mdl: mdl?, position }))
+        }))
+        .await
+    }
+}
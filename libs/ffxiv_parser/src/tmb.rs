@@ -0,0 +1,305 @@
+use alloc::vec::Vec;
+use core::{mem::size_of, str};
+
+use hashbrown::HashMap;
+use sqpack::{Package, Result};
+use util::{StrExt, cast, cast_array};
+
+// timeline runs at 30 frames per second
+const FRAMES_PER_SECOND: f32 = 30.;
+
+#[repr(C)]
+struct TimelineHeader {
+    _signature: u32,
+    _size: u32,
+    entry_count: u32,
+}
+
+#[repr(C)]
+struct TimelineEntryHeader {
+    magic: [u8; 4],
+    size: u32,
+}
+
+// TMAL
+#[repr(C)]
+struct TimelineActorList {
+    actor_offset: u32,
+    actor_count: u32,
+}
+
+// TMAC
+#[repr(C)]
+struct TimelineActor {
+    id: u16,
+    frame: u16,
+    _unk1: u32,
+    _unk2: u32,
+    track_offset: u32,
+    track_count: u32,
+}
+
+// TMTR
+#[repr(C)]
+struct TimelineTrack {
+    id: u16,
+    frame: u16,
+    event_offset: u32,
+    event_count: u32,
+}
+
+pub struct TmbActor {
+    pub id: u16,
+    pub frame: u16,
+    track_ids_offset: usize,
+    track_count: usize,
+}
+
+pub struct TmbTrack {
+    pub id: u16,
+    pub frame: u16,
+    event_ids_offset: usize,
+    event_count: usize,
+}
+
+// parameter layouts of commonly used C0xx events
+pub enum TmbEventKind<'a> {
+    // C012
+    Vfx {
+        path: &'a str,
+    },
+    // C063
+    Sound {
+        path: &'a str,
+        sound_index: u32,
+    },
+    // C006, attaches child object (e.g. weapon) bind point to parent bind point
+    Attach {
+        bind_point_id: u32,
+        child_object_id: u32,
+        child_point_id: u32,
+    },
+    Other,
+}
+
+// TMFC and C0xx entries. parameters follow id and frame.
+pub struct TmbEvent<'a> {
+    pub magic: [u8; 4],
+    pub id: u16,
+    pub frame: u16,
+    parameters: &'a [u32],
+    data: &'a [u8],
+    data_offset: usize,
+    string_pool_offset: usize,
+}
+
+impl<'a> TmbEvent<'a> {
+    pub fn name(&self) -> Option<&str> {
+        str::from_utf8(&self.magic).ok()
+    }
+
+    pub fn kind(&self) -> TmbEventKind<'a> {
+        let parameter = |index: usize| self.parameters.get(index).copied();
+
+        let kind = match &self.magic {
+            b"C012" => self.string_parameter(2).map(|path| TmbEventKind::Vfx { path }),
+            b"C063" => self
+                .string_parameter(2)
+                .zip(parameter(3))
+                .map(|(path, sound_index)| TmbEventKind::Sound { path, sound_index }),
+            b"C006" => parameter(2)
+                .zip(parameter(3))
+                .zip(parameter(4))
+                .map(|((bind_point_id, child_object_id), child_point_id)| TmbEventKind::Attach {
+                    bind_point_id,
+                    child_object_id,
+                    child_point_id,
+                }),
+            _ => None,
+        };
+
+        kind.unwrap_or(TmbEventKind::Other)
+    }
+
+    pub fn time(&self) -> f32 {
+        self.frame as f32 / FRAMES_PER_SECOND
+    }
+
+    pub fn parameters(&self) -> &'a [u32] {
+        self.parameters
+    }
+
+    // string parameters are stored as offset into string pool at the end of file
+    pub fn string_parameter(&self, index: usize) -> Option<&'a str> {
+        let offset = self.data_offset + *self.parameters.get(index)? as usize;
+        if offset < self.string_pool_offset || offset >= self.data.len() {
+            return None;
+        }
+
+        str::from_null_terminated_utf8(&self.data[offset..]).ok()
+    }
+}
+
+pub enum TmbEntry<'a> {
+    Actor(&'a TmbActor),
+    Track(&'a TmbTrack),
+    Curve(TmbEvent<'a>),
+    Event(TmbEvent<'a>),
+}
+
+impl TmbEntry<'_> {
+    pub fn id(&self) -> u16 {
+        match self {
+            TmbEntry::Actor(x) => x.id,
+            TmbEntry::Track(x) => x.id,
+            TmbEntry::Curve(x) | TmbEntry::Event(x) => x.id,
+        }
+    }
+}
+
+enum TimelineEntry {
+    Actor(TmbActor),
+    Track(TmbTrack),
+    Event { offset: usize, size: usize },
+}
+
+// Timeline
+pub struct Tmb {
+    data: Vec<u8>,
+    entries: Vec<TimelineEntry>,
+    entry_indices: HashMap<u16, usize>,
+    actor_list: Option<(usize, usize)>,
+    string_pool_offset: usize,
+}
+
+impl Tmb {
+    pub async fn new(package: &dyn Package, path: &str) -> Result<Self> {
+        let data = package.read_file(path).await?;
+
+        Ok(Self::from_raw(data))
+    }
+
+    pub fn from_raw(data: Vec<u8>) -> Self {
+        let header = cast::<TimelineHeader>(&data);
+
+        let mut entries = Vec::with_capacity(header.entry_count as usize);
+        let mut actor_list = None;
+        let mut offset = size_of::<TimelineHeader>();
+        for _ in 0..header.entry_count {
+            let entry_header = cast::<TimelineEntryHeader>(&data[offset..]);
+            // offsets in entries are relative to the end of entry header
+            let data_offset = offset + size_of::<TimelineEntryHeader>();
+
+            match &entry_header.magic {
+                b"TMDH" => {}
+                b"TMAL" => {
+                    let raw = cast::<TimelineActorList>(&data[data_offset..]);
+                    actor_list = Some((data_offset + raw.actor_offset as usize, raw.actor_count as usize));
+                }
+                b"TMAC" => {
+                    let raw = cast::<TimelineActor>(&data[data_offset..]);
+                    entries.push(TimelineEntry::Actor(TmbActor {
+                        id: raw.id,
+                        frame: raw.frame,
+                        track_ids_offset: data_offset + raw.track_offset as usize,
+                        track_count: raw.track_count as usize,
+                    }));
+                }
+                b"TMTR" => {
+                    let raw = cast::<TimelineTrack>(&data[data_offset..]);
+                    entries.push(TimelineEntry::Track(TmbTrack {
+                        id: raw.id,
+                        frame: raw.frame,
+                        event_ids_offset: data_offset + raw.event_offset as usize,
+                        event_count: raw.event_count as usize,
+                    }));
+                }
+                _ => entries.push(TimelineEntry::Event {
+                    offset,
+                    size: entry_header.size as usize,
+                }),
+            }
+
+            offset += entry_header.size as usize;
+        }
+
+        let mut result = Self {
+            data,
+            entries,
+            entry_indices: HashMap::new(),
+            actor_list,
+            string_pool_offset: offset,
+        };
+        result.entry_indices = (0..result.entries.len()).map(|x| (result.entry(x).id(), x)).collect();
+
+        result
+    }
+
+    // TMDH and TMAL are structural and not listed
+    pub fn entries(&self) -> Vec<TmbEntry<'_>> {
+        (0..self.entries.len()).map(|x| self.entry(x)).collect()
+    }
+
+    pub fn actors(&self) -> Vec<&TmbActor> {
+        let Some((ids_offset, count)) = self.actor_list else {
+            return Vec::new();
+        };
+
+        self.resolve_ids(ids_offset, count)
+            .into_iter()
+            .filter_map(|x| match x {
+                TmbEntry::Actor(x) => Some(x),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn tracks(&self, actor: &TmbActor) -> Vec<&TmbTrack> {
+        self.resolve_ids(actor.track_ids_offset, actor.track_count)
+            .into_iter()
+            .filter_map(|x| match x {
+                TmbEntry::Track(x) => Some(x),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn events(&self, track: &TmbTrack) -> Vec<TmbEntry<'_>> {
+        self.resolve_ids(track.event_ids_offset, track.event_count)
+    }
+
+    fn entry(&self, index: usize) -> TmbEntry<'_> {
+        match &self.entries[index] {
+            TimelineEntry::Actor(x) => TmbEntry::Actor(x),
+            TimelineEntry::Track(x) => TmbEntry::Track(x),
+            &TimelineEntry::Event { offset, size } => {
+                let magic = cast::<TimelineEntryHeader>(&self.data[offset..]).magic;
+                let data_offset = offset + size_of::<TimelineEntryHeader>();
+                let data = &self.data[data_offset..offset + size];
+
+                let event = TmbEvent {
+                    magic,
+                    id: *cast::<u16>(data),
+                    frame: *cast::<u16>(&data[2..]),
+                    parameters: cast_array::<u32>(&data[4..]),
+                    data: &self.data,
+                    data_offset,
+                    string_pool_offset: self.string_pool_offset,
+                };
+
+                if &magic == b"TMFC" {
+                    TmbEntry::Curve(event)
+                } else {
+                    TmbEntry::Event(event)
+                }
+            }
+        }
+    }
+
+    // entries are referenced by list of u16 ids
+    fn resolve_ids(&self, ids_offset: usize, count: usize) -> Vec<TmbEntry<'_>> {
+        let ids = &cast_array::<u16>(&self.data[ids_offset..])[..count];
+
+        ids.iter().filter_map(|x| self.entry_indices.get(x)).map(|&x| self.entry(x)).collect()
+    }
+}
@@ -0,0 +1,37 @@
+use ffxiv_parser::Cmp;
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn cmp_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let cmp = Cmp::new(&pack).await?;
+    // first list is eye colors, every color in it is opaque and they're not all same
+    let eye_colors = &cmp.colors()[..192];
+    assert!(eye_colors.iter().all(|x| x[3] == 255));
+    assert!(eye_colors.iter().any(|x| x != &eye_colors[0]));
+
+    // every playable race and tribe has sane scaling range
+    for race_id in 1..=8 {
+        for tribe_id in [race_id * 2 - 1, race_id * 2] {
+            let scaling = cmp.racial_scaling(race_id, tribe_id).unwrap();
+            for (min, max) in [
+                (scaling.male_min_size, scaling.male_max_size),
+                (scaling.female_min_size, scaling.female_max_size),
+            ] {
+                assert!(min > 0.5 && min <= max && max < 1.5, "race {race_id} tribe {tribe_id}: {min} {max}");
+            }
+        }
+    }
+
+    assert!(cmp.racial_scaling(0, 1).is_none());
+    assert!(cmp.racial_scaling(1, 0).is_none());
+
+    Ok(())
+}
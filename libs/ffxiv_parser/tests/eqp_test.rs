@@ -0,0 +1,20 @@
+use ffxiv_parser::{Eqp, EqpEntry};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn eqp_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let eqp = Eqp::new(&pack).await?;
+    let entry = eqp.get(6016);
+    assert!(entry.contains(EqpEntry::BODY_ENABLED));
+    assert!(entry.contains(EqpEntry::HEAD_ENABLED));
+
+    Ok(())
+}
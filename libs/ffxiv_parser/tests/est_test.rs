@@ -0,0 +1,38 @@
+use ffxiv_parser::{Est, Sklb};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn est_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let est = Est::new(&pack, Est::HEAD_PATH).await?;
+    assert!(est.get(0, 101).is_none());
+
+    // every midlander male face has its own skeleton
+    let est = Est::new(&pack, Est::FACE_PATH).await?;
+    assert_eq!(est.get(1, 101), Some(1));
+
+    // skeletons referenced by table exist
+    for path in [Est::HAIR_PATH, Est::HEAD_PATH] {
+        let est = Est::new(&pack, path).await?;
+        let (kind, prefix) = if path == Est::HAIR_PATH { ("hair", 'h') } else { ("met", 'm') };
+
+        let entries = est.entries().take(3).collect::<Vec<_>>();
+        assert!(!entries.is_empty());
+        for (set_id, body_id, skeleton_id) in entries {
+            assert_eq!(est.get(set_id, body_id), Some(skeleton_id));
+
+            let sklb_path =
+                format!("chara/human/c{body_id:04}/skeleton/{kind}/{prefix}{skeleton_id:04}/skl_c{body_id:04}{prefix}{skeleton_id:04}.sklb");
+            Sklb::new(&pack, &sklb_path).await?;
+        }
+    }
+
+    Ok(())
+}
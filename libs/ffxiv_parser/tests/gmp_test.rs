@@ -0,0 +1,26 @@
+use ffxiv_parser::{Gmp, Imc};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn gmp_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let gmp = Gmp::new(&pack).await?;
+    assert!(!gmp.get(0).enabled);
+
+    // visors are on real helmets
+    let enabled = (1..10000).filter(|&x| gmp.get(x).enabled).take(5).collect::<Vec<_>>();
+    assert!(!enabled.is_empty());
+    for set_id in enabled {
+        let imc = Imc::new(&pack, format!("chara/equipment/e{set_id:04}/e{set_id:04}.imc")).await?;
+        assert!(imc.has_part(0));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use ffxiv_parser::Imc;
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn imc_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let imc = Imc::new(&pack, "chara/equipment/e6016/e6016.imc").await?;
+    assert!(imc.variant_count() >= 1);
+    assert!(imc.has_part(1));
+    assert_eq!(imc.get(1, 1).unwrap().material_id, 1);
+
+    // equipment imc only has 5 parts
+    assert!(!imc.has_part(5));
+    assert!(imc.get(1, 5).is_none());
+    assert!(imc.get(1, 16).is_none());
+
+    Ok(())
+}
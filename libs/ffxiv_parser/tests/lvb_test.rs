@@ -13,6 +13,22 @@ async fn lvb_test() -> Result<()> {
 
     let lvb = Lvb::new(&pack, "bg/ffxiv/sea_s1/twn/s1t1/level/s1t1.lvb").await?;
     assert!(lvb.lgb_paths.iter().any(|x| x == "bg/ffxiv/sea_s1/twn/s1t1/level/bg.lgb"));
+    assert_eq!(lvb.terrain_path.as_deref(), Some("bg/ffxiv/sea_s1/twn/s1t1/bgplate"));
+
+    // scene settings
+    assert_eq!(lvb.environment.lcb_path.as_deref(), Some("bg/ffxiv/sea_s1/twn/s1t1/level/s1t1.lcb"));
+    assert!(lvb.environment.sky_visibility_path.as_deref().unwrap().ends_with(".svb"));
+
+    // limsa lominsa upper decks is territory type 128
+    let filter = lvb.layer_filters.iter().find(|x| x.territory_type_id == 128).unwrap();
+    assert!(!filter.layer_ids.is_empty());
+
+    // seasonal events decorate the city
+    assert!(!lvb.festivals.is_empty());
+    assert!(lvb.festivals.iter().all(|x| x.festival_id != 0 && !x.layer_ids.is_empty()));
+
+    // time flows in towns
+    assert_eq!(lvb.environment.time_of_day, None);
 
     Ok(())
 }
@@ -25,7 +25,13 @@ async fn mtrl_test() -> Result<()> {
 
         assert_eq!(mtrl.shader_name(), "characterlegacy.shpk");
 
-        assert!(mtrl.parameters()[0].parameter_type == MtrlParameterType::Normal)
+        assert!(mtrl.parameters()[0].parameter_type() == MtrlParameterType::Normal);
+
+        assert!(!mtrl.constants().is_empty());
+        for constant in mtrl.constants() {
+            assert!(mtrl.constant(constant.id).is_some());
+        }
+        assert!(mtrl.constant(0).is_none());
     }
 
     {
@@ -20,5 +20,42 @@ async fn pap_test() -> Result<()> {
     assert_eq!(hkx[4], b'\xce');
     assert_eq!(hkx[5], b'\xfa');
 
+    assert_eq!(pap.body_id(), 1101);
+
+    let animations = pap.animations();
+    assert_eq!(animations.len(), 2);
+    assert_eq!(animations[0].name(), Some("cbnm_id0"));
+    for (index, animation) in animations.iter().enumerate() {
+        assert_eq!(animation.havok_index as usize, index);
+        assert!(!animation.is_face());
+    }
+    let idle = pap.find_animation("cbnm_id0").unwrap();
+    assert_eq!(idle.havok_index, 0);
+
+    // one timeline per animation
+    for index in 0..animations.len() {
+        assert_eq!(&pap.timeline_data(index).unwrap()[..4], b"TMLB");
+    }
+    assert!(pap.timeline_data(animations.len()).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn face_pap_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pap = Pap::new(&pack, "chara/human/c1101/animation/f0001/resident/face.pap").await?;
+    assert_eq!(pap.body_id(), 1101);
+
+    let animations = pap.animations();
+    assert!(!animations.is_empty());
+    assert!(animations.iter().all(|x| x.is_face() && x.name().is_some()));
+
     Ok(())
 }
@@ -23,5 +23,9 @@ async fn pbd_test() -> Result<()> {
     let result = pbd.get_deform_matrices(601, 101);
     assert_eq!(result["j_ago"].to_cols_array()[0], 0.89393127);
 
+    assert!(pbd.body_ids().contains(&1801));
+    assert_eq!(pbd.parent_body_id(101), None);
+    assert_eq!(pbd.parent_body_id(201), Some(101));
+
     Ok(())
 }
@@ -0,0 +1,22 @@
+use ffxiv_parser::Pcb;
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn pcb_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pcb = Pcb::new(&pack, "bg/ffxiv/sea_s1/twn/s1t1/collision/tr0000.pcb").await?;
+    let soup = pcb.triangle_soup();
+    assert!(!soup.vertices.is_empty());
+    assert_eq!(soup.indices.len(), soup.materials.len() * 3);
+    assert!(soup.indices.iter().all(|&x| (x as usize) < soup.vertices.len()));
+    assert!(soup.to_obj().starts_with("v "));
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use ffxiv_parser::{Phyb, PhybCollisionShape};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[allow(clippy::float_cmp)]
+#[tokio::test]
+async fn phyb_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let phyb = Phyb::new(&pack, "chara/human/c0101/skeleton/hair/h0001/phy_c0101h0001.phyb").await?;
+
+    for collision_object in phyb.collision_objects() {
+        assert!(!collision_object.name.is_empty());
+        match collision_object.shape {
+            PhybCollisionShape::Capsule { radius, .. } | PhybCollisionShape::Ellipsoid { radius, .. } | PhybCollisionShape::Sphere { radius, .. } => {
+                assert!(radius >= 0.)
+            }
+            PhybCollisionShape::NormalPlane { thickness, .. } | PhybCollisionShape::ThreePointPlane { thickness, .. } => assert!(thickness >= 0.),
+        }
+    }
+
+    let simulators = phyb.simulators();
+    assert_eq!(simulators.len(), 1);
+
+    // hair falls straight down
+    let simulator = &simulators[0];
+    assert_eq!(simulator.gravity[0], 0.);
+    assert!(simulator.gravity[1] < 0.);
+    assert_eq!(simulator.gravity[2], 0.);
+    assert!(simulator.constraint_loop > 0);
+
+    // each chain hangs from a hair bone with at least a root and a tip node
+    assert!(!simulator.chains.is_empty());
+    for chain in &simulator.chains {
+        assert!(chain.damping > 0. && chain.damping <= 1.);
+        assert!(chain.nodes.len() >= 2);
+        assert!(chain.nodes.iter().all(|x| x.bone_name.starts_with("j_kami")));
+    }
+
+    Ok(())
+}
@@ -20,5 +20,10 @@ async fn sklb_test() -> Result<()> {
     assert_eq!(hkx[4], b'\xce');
     assert_eq!(hkx[5], b'\xfa');
 
+    assert_ne!(sklb.body_id(), 0);
+
+    let face_sklb = Sklb::new(&pack, "chara/human/c0101/skeleton/face/f0001/skl_c0101f0001.sklb").await?;
+    assert_eq!(face_sklb.parent_body_id(), Some(sklb.body_id()));
+
     Ok(())
 }
@@ -0,0 +1,24 @@
+use ffxiv_parser::{Lvb, Tera};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn tera_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let lvb = Lvb::new(&pack, "bg/ffxiv/sea_s1/twn/s1t1/level/s1t1.lvb").await?;
+    let tera = Tera::new(&pack, format!("{}/terrain.tera", lvb.terrain_path.unwrap())).await?;
+    assert!(tera.plate_count() > 0);
+    assert_eq!(tera.plate_path(0), "bg/ffxiv/sea_s1/twn/s1t1/bgplate/0000.mdl");
+
+    let plates = tera.read_plates(&pack).await?;
+    assert_eq!(plates.len(), tera.plate_count());
+    assert_eq!(plates[0].position, tera.plate_position(0));
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use ffxiv_parser::{Pap, Tmb, TmbEntry, TmbEventKind};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+#[tokio::test]
+async fn tmb_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pap = Pap::new(&pack, "chara/human/c1101/animation/a0001/bt_common/resident/idle.pap").await?;
+    let tmb = pap.timeline(0).unwrap();
+
+    let actors = tmb.actors();
+    assert!(!actors.is_empty());
+
+    for actor in actors {
+        for track in tmb.tracks(actor) {
+            for event in tmb.events(track) {
+                if let TmbEntry::Event(event) = event {
+                    assert!(event.name().unwrap().starts_with('C'));
+                    assert!(event.time() >= 0.);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::float_cmp)]
+#[tokio::test]
+async fn action_tmb_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let tmb = Tmb::new(&pack, "chara/action/magic/2bl_fire/mgc001.tmb").await?;
+
+    let events = tmb
+        .entries()
+        .into_iter()
+        .filter_map(|x| match x {
+            TmbEntry::Event(x) => Some(x),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // casting effect starts with the timeline
+    let vfx = events.iter().find(|x| matches!(x.kind(), TmbEventKind::Vfx { .. })).unwrap();
+    assert_eq!(vfx.name(), Some("C012"));
+    assert_eq!(vfx.frame, 0);
+    assert_eq!(vfx.time(), 0.);
+    let TmbEventKind::Vfx { path } = vfx.kind() else { unreachable!() };
+    assert_eq!(Some(path), vfx.string_parameter(2));
+    assert!(path.starts_with("vfx/") && path.ends_with(".avfx"));
+
+    let sound = events.iter().find(|x| matches!(x.kind(), TmbEventKind::Sound { .. })).unwrap();
+    assert_eq!(sound.name(), Some("C063"));
+    let TmbEventKind::Sound { path, .. } = sound.kind() else {
+        unreachable!()
+    };
+    assert!(path.starts_with("sound/") && path.ends_with(".scd"));
+
+    Ok(())
+}
+
+#[test]
+fn tmb_event_kind_test() {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"TMLB");
+    data.extend_from_slice(&0u32.to_le_bytes()); // size
+    data.extend_from_slice(&2u32.to_le_bytes()); // entry count
+
+    // string offsets are relative to the end of entry header
+    data.extend_from_slice(b"C012");
+    data.extend_from_slice(&24u32.to_le_bytes());
+    data.extend_from_slice(&[1, 0, 10, 0]); // id, frame
+    for parameter in [0u32, 0, 44] {
+        data.extend_from_slice(&parameter.to_le_bytes());
+    }
+
+    data.extend_from_slice(b"C063");
+    data.extend_from_slice(&28u32.to_le_bytes());
+    data.extend_from_slice(&[2, 0, 20, 0]);
+    for parameter in [0u32, 0, 31, 7] {
+        data.extend_from_slice(&parameter.to_le_bytes());
+    }
+
+    data.extend_from_slice(b"vfx/a.avfx\0sound/a.scd\0");
+
+    let tmb = Tmb::from_raw(data);
+    let entries = tmb.entries();
+
+    let TmbEntry::Event(vfx) = &entries[0] else { panic!() };
+    assert_eq!(vfx.frame, 10);
+    assert!(matches!(vfx.kind(), TmbEventKind::Vfx { path: "vfx/a.avfx" }));
+
+    let TmbEntry::Event(sound) = &entries[1] else { panic!() };
+    assert_eq!(sound.name(), Some("C063"));
+    assert!(matches!(
+        sound.kind(),
+        TmbEventKind::Sound {
+            path: "sound/a.scd",
+            sound_index: 7
+        }
+    ));
+}
@@ -0,0 +1,66 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::RefCell;
+
+use crate::{
+    interleaved_uncompressed_animation::frame_and_delta,
+    object::{HavokObject, HavokValue},
+    transform::HavokTransform,
+};
+
+// extracted motion of root bone. each sample has translation in xyz and rotation angle around up axis in w.
+pub struct HavokAnimatedReferenceFrame {
+    pub up: [f32; 4],
+    pub forward: [f32; 4],
+    pub duration: f32,
+    pub samples: Vec<[f32; 4]>,
+}
+
+impl HavokAnimatedReferenceFrame {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Option<Self> {
+        let root = object.borrow();
+        if &*root.object_type.name != "hkaDefaultAnimatedReferenceFrame" {
+            return None;
+        }
+
+        let to_vec4 = |x: &HavokValue| {
+            let x = x.as_vec();
+            [x[0], x[1], x[2], x[3]]
+        };
+
+        let up = to_vec4(root.get("up"));
+        let forward = to_vec4(root.get("forward"));
+        let duration = root.get("duration").as_real();
+        let samples = root.get("referenceFrameSamples").as_array().iter().map(to_vec4).collect::<Vec<_>>();
+
+        Some(Self {
+            up,
+            forward,
+            duration,
+            samples,
+        })
+    }
+
+    pub(crate) fn read(animation: &HavokObject) -> Option<Self> {
+        match animation.find("extractedMotion") {
+            Some(HavokValue::Object(x)) => Self::new(x.clone()),
+            _ => None,
+        }
+    }
+
+    // time in milliseconds
+    pub fn sample(&self, time: f32) -> HavokTransform {
+        if self.samples.is_empty() {
+            return HavokTransform::identity();
+        }
+
+        let (frame, delta) = frame_and_delta(time, self.duration, self.samples.len());
+        let a = self.samples[frame];
+        let b = self.samples[usize::min(frame + 1, self.samples.len() - 1)];
+        let sample = [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * delta);
+
+        let (sin, cos) = (sample[3] / 2.).sin_cos();
+        let rotation = [self.up[0] * sin, self.up[1] * sin, self.up[2] * sin, cos];
+
+        HavokTransform::from_trs([sample[0], sample[1], sample[2], 0.], rotation, [1., 1., 1., 1.])
+    }
+}
@@ -1,8 +1,66 @@
-use alloc::vec::Vec;
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
 
-use crate::transform::HavokTransform;
+use crate::{animated_reference_frame::HavokAnimatedReferenceFrame, object::HavokObject, transform::HavokTransform};
+
+pub struct HavokAnnotation {
+    // seconds from start of animation, as stored in file. sample functions take milliseconds.
+    pub time: f32,
+    pub text: String,
+}
+
+pub struct HavokAnnotationTrack {
+    pub name: String,
+    pub annotations: Vec<HavokAnnotation>,
+}
+
+impl HavokAnnotationTrack {
+    pub(crate) fn read_tracks(animation: &HavokObject) -> Vec<Self> {
+        let raw_tracks = match animation.find("annotationTracks") {
+            Some(x) => x.as_array(),
+            None => return Vec::new(),
+        };
+
+        raw_tracks
+            .iter()
+            .map(|x| {
+                let track = x.as_object();
+                let track = track.borrow();
+
+                let annotations = track.find("annotations").map(|x| x.as_array().as_slice()).unwrap_or_default();
+                let annotations = annotations
+                    .iter()
+                    .map(|x| {
+                        let annotation = x.as_object();
+                        let annotation = annotation.borrow();
+
+                        HavokAnnotation {
+                            time: annotation.get("time").as_real(),
+                            text: annotation.get("text").as_string().to_owned(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                Self {
+                    name: track.find("trackName").map(|x| x.as_string()).unwrap_or_default().to_owned(),
+                    annotations,
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+}
 
 pub trait HavokAnimation {
+    // in seconds
     fn duration(&self) -> f32;
+    // time in milliseconds
     fn sample(&self, time: f32) -> Vec<HavokTransform>;
+    // time in milliseconds
+    fn sample_floats(&self, time: f32) -> Vec<f32>;
+    fn annotations(&self) -> &[HavokAnnotationTrack];
+    fn extracted_motion(&self) -> Option<&HavokAnimatedReferenceFrame>;
+
+    // root motion accumulated since start of animation, time in milliseconds
+    fn sample_root_motion(&self, time: f32) -> Option<HavokTransform> {
+        self.extracted_motion().map(|x| x.sample(time))
+    }
 }
@@ -1,7 +1,16 @@
-use alloc::{boxed::Box, rc::Rc, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, rc::Rc, vec::Vec};
 use core::cell::RefCell;
 
-use crate::{animation::HavokAnimation, object::HavokObject, spline_compressed_animation::HavokSplineCompressedAnimation};
+use crate::{
+    animation::HavokAnimation,
+    delta_compressed_animation::HavokDeltaCompressedAnimation,
+    error::{HavokError, Result},
+    interleaved_uncompressed_animation::HavokInterleavedUncompressedAnimation,
+    object::HavokObject,
+    quantized_animation::HavokQuantizedAnimation,
+    spline_compressed_animation::HavokSplineCompressedAnimation,
+    transform::HavokTransform,
+};
 
 #[repr(u8)]
 pub enum HavokAnimationBlendHint {
@@ -21,29 +30,63 @@ impl HavokAnimationBlendHint {
 
 pub struct HavokAnimationBinding {
     pub transform_track_to_bone_indices: Vec<u16>,
+    pub float_track_to_float_slot_indices: Vec<u16>,
     pub blend_hint: HavokAnimationBlendHint,
     pub animation: Box<dyn HavokAnimation>,
 }
 
 impl HavokAnimationBinding {
-    pub fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Result<Self> {
         let root = object.borrow();
 
         let raw_transform_track_to_bone_indices = root.get("transformTrackToBoneIndices").as_array();
         let transform_track_to_bone_indices = raw_transform_track_to_bone_indices.iter().map(|x| x.as_int() as u16).collect::<Vec<_>>();
 
+        let float_track_to_float_slot_indices = root
+            .find("floatTrackToFloatSlotIndices")
+            .map(|x| x.as_array().iter().map(|x| x.as_int() as u16).collect::<Vec<_>>())
+            .unwrap_or_default();
+
         let blend_hint = HavokAnimationBlendHint::from_raw(root.get("blendHint").as_int() as u8);
 
         let raw_animation = root.get("animation").as_object();
-        let animation = match &*raw_animation.borrow().object_type.name {
+        let animation_type = raw_animation.borrow().object_type.name.clone();
+        let animation: Box<dyn HavokAnimation> = match &*animation_type {
             "hkaSplineCompressedAnimation" => Box::new(HavokSplineCompressedAnimation::new(raw_animation.clone())),
-            _ => panic!(),
+            "hkaInterleavedUncompressedAnimation" => Box::new(HavokInterleavedUncompressedAnimation::new(raw_animation.clone())),
+            "hkaQuantizedAnimation" => Box::new(HavokQuantizedAnimation::new(raw_animation.clone())),
+            "hkaDeltaCompressedAnimation" => Box::new(HavokDeltaCompressedAnimation::new(raw_animation.clone())),
+            _ => return Err(HavokError::UnsupportedAnimationType(animation_type.as_ref().to_owned())),
         };
 
-        Self {
+        Ok(Self {
             transform_track_to_bone_indices,
+            float_track_to_float_slot_indices,
             blend_hint,
             animation,
+        })
+    }
+
+    // time in milliseconds. bones without track keep reference pose.
+    pub fn sample_pose(&self, reference_pose: &[HavokTransform], time: f32) -> Vec<HavokTransform> {
+        let mut result = reference_pose.to_vec();
+
+        self.blend_pose(&mut result, time, 1.);
+
+        result
+    }
+
+    // layers animation onto pose, weight 1 fully applies animation
+    pub fn blend_pose(&self, pose: &mut [HavokTransform], time: f32, weight: f32) {
+        let tracks = self.animation.sample(time);
+
+        for (track, &bone_index) in tracks.iter().zip(self.transform_track_to_bone_indices.iter()) {
+            let bone = &mut pose[bone_index as usize];
+
+            *bone = match self.blend_hint {
+                HavokAnimationBlendHint::Normal => bone.slerp(track, weight),
+                HavokAnimationBlendHint::Additive => bone.apply_additive(track, weight),
+            };
         }
     }
 }
@@ -1,7 +1,7 @@
 use alloc::{rc::Rc, vec::Vec};
 use core::cell::RefCell;
 
-use crate::{animation_binding::HavokAnimationBinding, object::HavokObject, skeleton::HavokSkeleton};
+use crate::{animation_binding::HavokAnimationBinding, error::Result, object::HavokObject, skeleton::HavokSkeleton};
 
 pub struct HavokAnimationContainer {
     pub skeletons: Vec<HavokSkeleton>,
@@ -9,15 +9,18 @@ pub struct HavokAnimationContainer {
 }
 
 impl HavokAnimationContainer {
-    pub fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Result<Self> {
         let root = object.borrow();
 
         let raw_skeletons = root.get("skeletons").as_array();
         let skeletons = raw_skeletons.iter().map(|x| HavokSkeleton::new(x.as_object())).collect::<Vec<_>>();
 
         let raw_bindings = root.get("bindings").as_array();
-        let bindings = raw_bindings.iter().map(|x| HavokAnimationBinding::new(x.as_object())).collect::<Vec<_>>();
+        let bindings = raw_bindings
+            .iter()
+            .map(|x| HavokAnimationBinding::new(x.as_object()))
+            .collect::<Result<Vec<_>>>()?;
 
-        Self { skeletons, bindings }
+        Ok(Self { skeletons, bindings })
     }
 }
@@ -6,10 +6,10 @@ use hashbrown::HashMap;
 use util::SliceByteOrderExt;
 
 use crate::byte_reader::ByteReader;
-use crate::object::{HavokInteger, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokRootObject, HavokValue, HavokValueType};
+use crate::object::{HavokInteger, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokReal, HavokRootObject, HavokValue, HavokValueType};
 
 #[repr(i8)]
-enum HavokTagType {
+pub(crate) enum HavokTagType {
     Eof = -1,
     Invalid = 0,
     FileInfo = 1,
@@ -117,6 +117,10 @@ impl<'a> HavokBinaryTagFileReader<'a> {
         let object_type_index = self.read_packed_int();
         let object_type = self.remembered_types[object_type_index as usize].clone();
 
+        self.read_object_data(object_type)
+    }
+
+    fn read_object_data(&mut self, object_type: Rc<HavokObjectType>) -> HavokObject {
         let members = object_type.members();
         let data_existence = self.read_bit_field(members.len());
 
@@ -151,11 +155,22 @@ impl<'a> HavokBinaryTagFileReader<'a> {
                 HavokValueType::REAL => HavokValue::Real(self.reader.read_f32_le()),
                 HavokValueType::STRING => HavokValue::String(self.read_string()),
                 HavokValueType::OBJECT => HavokValue::ObjectReference(self.read_packed_int() as usize),
+                HavokValueType::STRUCT => HavokValue::Object(self.read_struct(member)),
+                _ if member.type_.is_vec() => HavokValue::Vec((0..member.type_.vec_size()).map(|_| self.reader.read_f32_le()).collect::<Vec<_>>()),
                 _ => panic!("unimplemented {}", member.type_.bits()),
             }
         }
     }
 
+    fn read_struct(&mut self, member: &HavokObjectTypeMember) -> Rc<RefCell<HavokObject>> {
+        let target_type = self.find_type(member.class_name.as_ref().unwrap());
+
+        let object = Rc::new(RefCell::new(self.read_object_data(target_type)));
+        self.objects.push(object.clone());
+
+        object
+    }
+
     fn read_array(&mut self, member: &HavokObjectTypeMember, array_len: usize) -> Vec<HavokValue> {
         let base_type = member.type_.base_type();
         match base_type {
@@ -177,6 +192,14 @@ impl<'a> HavokBinaryTagFileReader<'a> {
                     if data_existence[member_index] {
                         if member.type_.is_tuple() {
                             panic!()
+                        } else if member.type_.is_array() {
+                            // lengths of each item's array come first, followed by all items
+                            let lengths = (0..array_len).map(|_| self.read_packed_int() as usize).collect::<Vec<_>>();
+                            let mut data = self.read_array(member, lengths.iter().sum()).into_iter();
+                            for (index, length) in lengths.into_iter().enumerate() {
+                                let item = HavokValue::Array(data.by_ref().take(length).collect::<Vec<_>>());
+                                result_objects[index].borrow_mut().set(member_index, item);
+                            }
                         } else {
                             let data = self.read_array(member, array_len);
                             for (index, item) in data.into_iter().enumerate() {
@@ -320,15 +343,16 @@ impl<'a> HavokBinaryTagFileReader<'a> {
     }
 
     fn default_value(type_: HavokValueType) -> HavokValue {
-        if type_.is_vec() {
-            HavokValue::Array((0..type_.vec_size()).map(|_| Self::default_value(type_.base_type())).collect::<Vec<_>>())
-        } else if type_.is_array() || type_.is_tuple() {
+        if type_.is_array() || type_.is_tuple() {
             HavokValue::Array(Vec::new())
+        } else if type_.is_vec() {
+            HavokValue::Vec(vec![HavokReal::default(); type_.vec_size() as usize])
         } else {
             match type_ {
                 HavokValueType::EMPTY => HavokValue::Integer(HavokInteger::default()),
                 HavokValueType::BYTE => HavokValue::Integer(HavokInteger::default()),
                 HavokValueType::INT => HavokValue::Integer(HavokInteger::default()),
+                HavokValueType::REAL => HavokValue::Real(HavokReal::default()),
                 HavokValueType::OBJECT => HavokValue::ObjectReference(0),
                 _ => panic!("unimplemented {}", type_.bits()),
             }
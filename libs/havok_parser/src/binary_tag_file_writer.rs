@@ -0,0 +1,291 @@
+use alloc::{rc::Rc, vec, vec::Vec};
+use core::cell::RefCell;
+
+use hashbrown::HashMap;
+
+use crate::binary_tag_file_reader::HavokTagType;
+use crate::object::{HavokInteger, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokRootObject, HavokValue, HavokValueType};
+
+pub struct HavokBinaryTagFileWriter {
+    data: Vec<u8>,
+    remembered_strings: Vec<Rc<str>>,
+    remembered_types: HashMap<Rc<str>, usize>,
+    remembered_type_count: usize,
+    object_indices: HashMap<*const RefCell<HavokObject>, usize>,
+    objects: Vec<Rc<RefCell<HavokObject>>>,
+}
+
+impl HavokBinaryTagFileWriter {
+    pub fn write(root: &HavokRootObject) -> Vec<u8> {
+        let mut writer = Self::new();
+
+        writer.do_write(root.object());
+
+        writer.data
+    }
+
+    fn new() -> Self {
+        // mirrors initial state of reader
+        let remembered_strings = vec![Rc::from("string"), Rc::from("")];
+        let remembered_types = HashMap::new();
+
+        Self {
+            data: Vec::new(),
+            remembered_strings,
+            remembered_types,
+            remembered_type_count: 1,
+            object_indices: HashMap::new(),
+            objects: Vec::new(),
+        }
+    }
+
+    fn do_write(&mut self, root: Rc<RefCell<HavokObject>>) {
+        self.data.extend_from_slice(&0xCAB0_0D1Eu32.to_le_bytes());
+        self.data.extend_from_slice(&0xD011_FACEu32.to_le_bytes());
+
+        self.write_packed_int(HavokTagType::FileInfo as HavokInteger);
+        self.write_packed_int(3);
+
+        // object index 0 is null, root object comes first
+        self.collect_object(root);
+
+        let objects = self.objects.clone();
+        for object in objects {
+            let object = object.borrow();
+
+            self.write_types(&object);
+
+            self.write_packed_int(HavokTagType::ObjectRemember as HavokInteger);
+            let type_index = self.remembered_types[&object.object_type.name];
+            self.write_packed_int(type_index as HavokInteger);
+            self.write_object_data(&object);
+        }
+
+        self.write_packed_int(HavokTagType::FileEnd as HavokInteger);
+    }
+
+    fn is_null(object: &Rc<RefCell<HavokObject>>) -> bool {
+        &*object.borrow().object_type.name == "object"
+    }
+
+    fn collect_object(&mut self, object: Rc<RefCell<HavokObject>>) {
+        if Self::is_null(&object) || self.object_indices.contains_key(&Rc::as_ptr(&object)) {
+            return;
+        }
+
+        self.objects.push(object.clone());
+        self.object_indices.insert(Rc::as_ptr(&object), self.objects.len());
+
+        self.collect_references(&object.borrow());
+    }
+
+    // structs are written inline, so only objects referenced from them are collected
+    fn collect_references(&mut self, object: &HavokObject) {
+        for (index, member) in object.object_type.members().into_iter().enumerate() {
+            let values = match object.value(index) {
+                Some(HavokValue::Array(x)) => x.iter().collect::<Vec<_>>(),
+                Some(x) => vec![x],
+                None => continue,
+            };
+
+            for value in values {
+                if let HavokValue::Object(x) = value {
+                    if member.type_.base_type() == HavokValueType::STRUCT {
+                        self.collect_references(&x.borrow());
+                    } else {
+                        self.collect_object(x.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    // type tags can't appear in middle of object, so types of nested structs are written beforehand
+    fn write_types(&mut self, object: &HavokObject) {
+        self.write_type(&object.object_type);
+
+        for (index, member) in object.object_type.members().into_iter().enumerate() {
+            if member.type_.base_type() != HavokValueType::STRUCT {
+                continue;
+            }
+
+            match object.value(index) {
+                Some(HavokValue::Array(x)) => x.iter().for_each(|x| self.write_types(&x.as_object().borrow())),
+                Some(HavokValue::Object(x)) => self.write_types(&x.borrow()),
+                _ => {}
+            }
+        }
+    }
+
+    fn write_type(&mut self, object_type: &HavokObjectType) {
+        if &*object_type.name == "object" || self.remembered_types.contains_key(&object_type.name) {
+            return;
+        }
+
+        let parent_index = if let Some(parent) = object_type.parent() {
+            self.write_type(parent);
+
+            self.remembered_types.get(&parent.name).copied().unwrap_or(0)
+        } else {
+            0
+        };
+
+        self.write_packed_int(HavokTagType::Type as HavokInteger);
+        self.write_string(&object_type.name);
+        self.write_packed_int(0); // version
+        self.write_packed_int(parent_index as HavokInteger);
+        self.write_packed_int(object_type.local_members().len() as HavokInteger);
+
+        for member in object_type.local_members() {
+            self.write_string(&member.name);
+            self.write_packed_int(member.type_.bits() as HavokInteger);
+
+            if member.type_.is_tuple() {
+                self.write_packed_int(member.tuple_size as HavokInteger);
+            }
+            if member.type_.base_type() == HavokValueType::OBJECT || member.type_.base_type() == HavokValueType::STRUCT {
+                self.write_string(member.class_name.as_deref().unwrap_or(""));
+            }
+        }
+
+        self.remembered_types.insert(object_type.name.clone(), self.remembered_type_count);
+        self.remembered_type_count += 1;
+    }
+
+    // empty arrays and missing values are left to reader defaults
+    fn is_member_written(value: Option<&HavokValue>) -> bool {
+        match value {
+            Some(HavokValue::Array(x)) => !x.is_empty(),
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    fn write_object_data(&mut self, object: &HavokObject) {
+        let members = object.object_type.members();
+
+        let existence = (0..members.len())
+            .map(|index| Self::is_member_written(object.value(index)))
+            .collect::<Vec<_>>();
+        self.write_bit_field(&existence);
+
+        for (index, member) in members.into_iter().enumerate() {
+            if existence[index] {
+                self.write_object_member_value(member, object.value(index).unwrap());
+            }
+        }
+    }
+
+    fn write_object_member_value(&mut self, member: &HavokObjectTypeMember, value: &HavokValue) {
+        if member.type_.is_array() {
+            let items = value.as_array().iter().collect::<Vec<_>>();
+
+            self.write_packed_int(items.len() as HavokInteger);
+            self.write_array(member, &items);
+        } else {
+            match member.type_ {
+                HavokValueType::BYTE => self.data.push(value.as_int() as u8),
+                HavokValueType::INT => self.write_packed_int(value.as_int()),
+                HavokValueType::REAL => self.data.extend_from_slice(&value.as_real().to_le_bytes()),
+                HavokValueType::STRING => self.write_string(value.as_string()),
+                HavokValueType::OBJECT => {
+                    let index = self.object_index(&value.as_object());
+                    self.write_packed_int(index as HavokInteger)
+                }
+                HavokValueType::STRUCT => self.write_object_data(&value.as_object().borrow()),
+                _ if member.type_.is_vec() => value.as_vec().iter().for_each(|x| self.data.extend_from_slice(&x.to_le_bytes())),
+                _ => panic!("unimplemented {}", member.type_.bits()),
+            }
+        }
+    }
+
+    fn write_array(&mut self, member: &HavokObjectTypeMember, items: &[&HavokValue]) {
+        match member.type_.base_type() {
+            HavokValueType::STRING => items.iter().for_each(|x| self.write_string(x.as_string())),
+            HavokValueType::STRUCT => {
+                let objects = items.iter().map(|x| x.as_object()).collect::<Vec<_>>();
+                let objects = objects.iter().map(|x| x.borrow()).collect::<Vec<_>>();
+                let target_type = objects[0].object_type.clone();
+
+                // struct of array. nested arrays which are all empty are omitted.
+                let existence = (0..target_type.member_count())
+                    .map(|index| {
+                        let values = objects.iter().map(|x| x.value(index)).collect::<Option<Vec<_>>>();
+
+                        values.is_some_and(|x| x.iter().any(|x| Self::is_member_written(Some(x))))
+                    })
+                    .collect::<Vec<_>>();
+                self.write_bit_field(&existence);
+
+                for (member_index, member) in target_type.members().into_iter().enumerate() {
+                    if existence[member_index] {
+                        let values = objects.iter().map(|x| x.value(member_index).unwrap()).collect::<Vec<_>>();
+
+                        if member.type_.is_array() {
+                            // lengths of each item's array come first, followed by all items
+                            values.iter().for_each(|x| self.write_packed_int(x.as_array().len() as HavokInteger));
+
+                            let values = values.iter().flat_map(|x| x.as_array().iter()).collect::<Vec<_>>();
+                            self.write_array(member, &values);
+                        } else {
+                            self.write_array(member, &values);
+                        }
+                    }
+                }
+            }
+            HavokValueType::OBJECT => items.iter().for_each(|x| {
+                let index = self.object_index(&x.as_object());
+                self.write_packed_int(index as HavokInteger)
+            }),
+            HavokValueType::BYTE => items.iter().for_each(|x| self.data.push(x.as_int() as u8)),
+            HavokValueType::INT => {
+                self.write_packed_int(4); // integer size
+                items.iter().for_each(|x| self.write_packed_int(x.as_int()))
+            }
+            HavokValueType::REAL => items.iter().for_each(|x| self.data.extend_from_slice(&x.as_real().to_le_bytes())),
+            HavokValueType::VEC4 | HavokValueType::VEC8 | HavokValueType::VEC12 | HavokValueType::VEC16 => items.iter().for_each(|x| {
+                x.as_vec().iter().for_each(|x| self.data.extend_from_slice(&x.to_le_bytes()));
+            }),
+            _ => panic!("unimplemented {} {}", member.type_.bits(), member.type_.base_type().bits()),
+        }
+    }
+
+    fn object_index(&self, object: &Rc<RefCell<HavokObject>>) -> usize {
+        self.object_indices.get(&Rc::as_ptr(object)).copied().unwrap_or(0)
+    }
+
+    fn write_string(&mut self, string: &str) {
+        // remembered string 0 can't be referenced as -0 is 0
+        if let Some(index) = self.remembered_strings.iter().skip(1).position(|x| &**x == string) {
+            self.write_packed_int(-(index as HavokInteger + 1));
+            return;
+        }
+
+        self.write_packed_int(string.len() as HavokInteger);
+        self.data.extend_from_slice(string.as_bytes());
+        self.remembered_strings.push(Rc::from(string));
+    }
+
+    fn write_bit_field(&mut self, bits: &[bool]) {
+        for chunk in bits.chunks(8) {
+            let byte = chunk.iter().enumerate().fold(0u8, |byte, (index, &bit)| byte | ((bit as u8) << index));
+            self.data.push(byte);
+        }
+    }
+
+    fn write_packed_int(&mut self, value: HavokInteger) {
+        let neg = (value < 0) as u8;
+        let mut value = value.unsigned_abs();
+
+        let mut byte = (((value & 0x3f) as u8) << 1) | neg;
+        value >>= 6;
+
+        while value != 0 {
+            self.data.push(byte | 0x80);
+
+            byte = (value & 0x7f) as u8;
+            value >>= 7;
+        }
+        self.data.push(byte);
+    }
+}
@@ -0,0 +1,278 @@
+use alloc::{rc::Rc, vec, vec::Vec};
+use core::cell::RefCell;
+
+use hashbrown::HashMap;
+
+use crate::{
+    animation_binding::HavokAnimationBlendHint,
+    error::{HavokError, Result},
+    object::{HavokInteger, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokRootObject, HavokValue, HavokValueType},
+    skeleton::HavokSkeleton,
+    transform::HavokTransform,
+};
+
+// hkaAnimation::AnimationType
+const HK_INTERLEAVED_ANIMATION: HavokInteger = 1;
+
+struct HavokTypes {
+    root_level_container: Rc<HavokObjectType>,
+    named_variant: Rc<HavokObjectType>,
+    animation_container: Rc<HavokObjectType>,
+    skeleton: Rc<HavokObjectType>,
+    bone: Rc<HavokObjectType>,
+    animation_binding: Rc<HavokObjectType>,
+    interleaved_uncompressed_animation: Rc<HavokObjectType>,
+}
+
+impl HavokTypes {
+    fn new() -> Self {
+        let referenced_object = Rc::new(HavokObjectType::new(Rc::from("hkReferencedObject"), None, Vec::new()));
+        let animation = Self::object_type(
+            "hkaAnimation",
+            Some(referenced_object.clone()),
+            &[
+                ("type", HavokValueType::INT, None),
+                ("duration", HavokValueType::REAL, None),
+                ("numberOfTransformTracks", HavokValueType::INT, None),
+                ("numberOfFloatTracks", HavokValueType::INT, None),
+                ("extractedMotion", HavokValueType::OBJECT, Some("hkaAnimatedReferenceFrame")),
+                ("annotationTracks", HavokValueType::ARRAYSTRUCT, Some("hkaAnnotationTrack")),
+            ],
+        );
+
+        Self {
+            root_level_container: Self::object_type(
+                "hkRootLevelContainer",
+                None,
+                &[("namedVariants", HavokValueType::ARRAYSTRUCT, Some("hkRootLevelContainerNamedVariant"))],
+            ),
+            named_variant: Self::object_type(
+                "hkRootLevelContainerNamedVariant",
+                None,
+                &[
+                    ("name", HavokValueType::STRING, None),
+                    ("className", HavokValueType::STRING, None),
+                    ("variant", HavokValueType::OBJECT, Some("hkReferencedObject")),
+                ],
+            ),
+            animation_container: Self::object_type(
+                "hkaAnimationContainer",
+                Some(referenced_object.clone()),
+                &[
+                    ("skeletons", HavokValueType::ARRAYOBJECT, Some("hkaSkeleton")),
+                    ("animations", HavokValueType::ARRAYOBJECT, Some("hkaAnimation")),
+                    ("bindings", HavokValueType::ARRAYOBJECT, Some("hkaAnimationBinding")),
+                    ("attachments", HavokValueType::ARRAYOBJECT, Some("hkaBoneAttachment")),
+                    ("skins", HavokValueType::ARRAYOBJECT, Some("hkaMeshBinding")),
+                ],
+            ),
+            skeleton: Self::object_type(
+                "hkaSkeleton",
+                Some(referenced_object.clone()),
+                &[
+                    ("name", HavokValueType::STRING, None),
+                    ("parentIndices", HavokValueType::ARRAYINT, None),
+                    ("bones", HavokValueType::ARRAYSTRUCT, Some("hkaBone")),
+                    ("referencePose", HavokValueType::ARRAYVEC12, None),
+                    ("referenceFloats", HavokValueType::ARRAYREAL, None),
+                    ("floatSlots", HavokValueType::ARRAYSTRING, None),
+                ],
+            ),
+            bone: Self::object_type(
+                "hkaBone",
+                None,
+                &[("name", HavokValueType::STRING, None), ("lockTranslation", HavokValueType::BYTE, None)],
+            ),
+            animation_binding: Self::object_type(
+                "hkaAnimationBinding",
+                Some(referenced_object),
+                &[
+                    ("originalSkeletonName", HavokValueType::STRING, None),
+                    ("animation", HavokValueType::OBJECT, Some("hkaAnimation")),
+                    ("transformTrackToBoneIndices", HavokValueType::ARRAYINT, None),
+                    ("floatTrackToFloatSlotIndices", HavokValueType::ARRAYINT, None),
+                    ("partitionIndices", HavokValueType::ARRAYINT, None),
+                    ("blendHint", HavokValueType::BYTE, None),
+                ],
+            ),
+            interleaved_uncompressed_animation: Self::object_type(
+                "hkaInterleavedUncompressedAnimation",
+                Some(animation),
+                &[
+                    ("transforms", HavokValueType::ARRAYVEC12, None),
+                    ("floats", HavokValueType::ARRAYREAL, None),
+                ],
+            ),
+        }
+    }
+
+    fn object_type(name: &str, parent: Option<Rc<HavokObjectType>>, members: &[(&str, HavokValueType, Option<&str>)]) -> Rc<HavokObjectType> {
+        let members = members
+            .iter()
+            .map(|&(name, type_, class_name)| HavokObjectTypeMember::new(Rc::from(name), type_, 0, class_name.map(Rc::from)))
+            .collect::<Vec<_>>();
+
+        Rc::new(HavokObjectType::new(Rc::from(name), parent, members))
+    }
+}
+
+// builds object graph of hkaAnimationContainer, to be written with HavokBinaryTagFileWriter
+pub struct HavokAnimationContainerBuilder {
+    types: HavokTypes,
+    skeletons: Vec<HavokValue>,
+    animations: Vec<HavokValue>,
+    bindings: Vec<HavokValue>,
+}
+
+impl HavokAnimationContainerBuilder {
+    pub fn new() -> Self {
+        Self {
+            types: HavokTypes::new(),
+            skeletons: Vec::new(),
+            animations: Vec::new(),
+            bindings: Vec::new(),
+        }
+    }
+
+    pub fn add_skeleton(&mut self, name: &str, skeleton: &HavokSkeleton) -> &mut Self {
+        let bones = skeleton
+            .bone_names
+            .iter()
+            .map(|x| {
+                HavokValue::Object(Self::object(
+                    &self.types.bone,
+                    vec![HavokValue::String(Rc::from(x.as_str())), HavokValue::Integer(0)],
+                ))
+            })
+            .collect::<Vec<_>>();
+        let parent_indices = skeleton.parent_indices.iter().map(|&x| HavokValue::Integer(x as HavokInteger)).collect();
+        let reference_pose = skeleton.reference_pose.iter().map(Self::transform).collect();
+
+        self.skeletons.push(HavokValue::Object(Self::object(
+            &self.types.skeleton,
+            vec![
+                HavokValue::String(Rc::from(name)),
+                HavokValue::Array(parent_indices),
+                HavokValue::Array(bones),
+                HavokValue::Array(reference_pose),
+                HavokValue::Array(Vec::new()),
+                HavokValue::Array(Vec::new()),
+            ],
+        )));
+
+        self
+    }
+
+    // frames are list of per-track transforms
+    pub fn add_uncompressed_animation(
+        &mut self,
+        skeleton_name: &str,
+        transform_track_to_bone_indices: &[u16],
+        blend_hint: HavokAnimationBlendHint,
+        duration: f32,
+        frames: &[Vec<HavokTransform>],
+    ) -> Result<&mut Self> {
+        let number_of_transform_tracks = transform_track_to_bone_indices.len();
+        if let Some(frame) = frames.iter().find(|x| x.len() != number_of_transform_tracks) {
+            return Err(HavokError::TrackCountMismatch {
+                expected: number_of_transform_tracks,
+                actual: frame.len(),
+            });
+        }
+        let transforms = frames.iter().flat_map(|x| x.iter().map(Self::transform)).collect::<Vec<_>>();
+
+        let animation = Self::object(
+            &self.types.interleaved_uncompressed_animation,
+            vec![
+                HavokValue::Integer(HK_INTERLEAVED_ANIMATION),
+                HavokValue::Real(duration),
+                HavokValue::Integer(number_of_transform_tracks as HavokInteger),
+                HavokValue::Integer(0),
+                HavokValue::Object(Self::null_object()),
+                HavokValue::Array(Vec::new()),
+                HavokValue::Array(transforms),
+                HavokValue::Array(Vec::new()),
+            ],
+        );
+
+        let binding = Self::object(
+            &self.types.animation_binding,
+            vec![
+                HavokValue::String(Rc::from(skeleton_name)),
+                HavokValue::Object(animation.clone()),
+                HavokValue::Array(
+                    transform_track_to_bone_indices
+                        .iter()
+                        .map(|&x| HavokValue::Integer(x as HavokInteger))
+                        .collect(),
+                ),
+                HavokValue::Array(Vec::new()),
+                HavokValue::Array(Vec::new()),
+                HavokValue::Integer(blend_hint as HavokInteger),
+            ],
+        );
+
+        self.animations.push(HavokValue::Object(animation));
+        self.bindings.push(HavokValue::Object(binding));
+
+        Ok(self)
+    }
+
+    pub fn build(self) -> HavokRootObject {
+        let animation_container = Self::object(
+            &self.types.animation_container,
+            vec![
+                HavokValue::Array(self.skeletons),
+                HavokValue::Array(self.animations),
+                HavokValue::Array(self.bindings),
+                HavokValue::Array(Vec::new()),
+                HavokValue::Array(Vec::new()),
+            ],
+        );
+
+        let named_variant = Self::object(
+            &self.types.named_variant,
+            vec![
+                HavokValue::String(Rc::from("Merged Animation Container")),
+                HavokValue::String(Rc::from("hkaAnimationContainer")),
+                HavokValue::Object(animation_container),
+            ],
+        );
+
+        HavokRootObject::new(Self::object(
+            &self.types.root_level_container,
+            vec![HavokValue::Array(vec![HavokValue::Object(named_variant)])],
+        ))
+    }
+
+    fn object(object_type: &Rc<HavokObjectType>, values: Vec<HavokValue>) -> Rc<RefCell<HavokObject>> {
+        let data = values.into_iter().enumerate().collect::<HashMap<_, _>>();
+
+        Rc::new(RefCell::new(HavokObject::new(object_type.clone(), data)))
+    }
+
+    fn null_object() -> Rc<RefCell<HavokObject>> {
+        Rc::new(RefCell::new(HavokObject::new(
+            Rc::new(HavokObjectType::new(Rc::from("object"), None, Vec::new())),
+            HashMap::new(),
+        )))
+    }
+
+    fn transform(transform: &HavokTransform) -> HavokValue {
+        HavokValue::Vec(
+            transform
+                .translation
+                .iter()
+                .chain(transform.rotation.iter())
+                .chain(transform.scale.iter())
+                .copied()
+                .collect(),
+        )
+    }
+}
+
+impl Default for HavokAnimationContainerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
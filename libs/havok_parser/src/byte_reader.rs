@@ -27,6 +27,18 @@ impl<'a> ByteReader<'a> {
         result
     }
 
+    pub fn read_u32_le(&mut self) -> u32 {
+        let result = u32::from_le_bytes([
+            self.data[self.cursor],
+            self.data[self.cursor + 1],
+            self.data[self.cursor + 2],
+            self.data[self.cursor + 3],
+        ]);
+        self.cursor += size_of::<u32>();
+
+        result
+    }
+
     pub fn read_f32_le(&mut self) -> f32 {
         let result = f32::from_le_bytes([
             self.data[self.cursor],
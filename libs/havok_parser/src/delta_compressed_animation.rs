@@ -0,0 +1,232 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::RefCell;
+
+use crate::{
+    animated_reference_frame::HavokAnimatedReferenceFrame,
+    animation::{HavokAnimation, HavokAnnotationTrack},
+    byte_reader::ByteReader,
+    interleaved_uncompressed_animation::frame_and_delta,
+    object::HavokObject,
+    transform::HavokTransform,
+};
+
+// translation xyz, rotation xyzw, scale xyz
+const DOFS_PER_TRACK: usize = 10;
+
+struct QuantizationFormat {
+    num_d: usize,
+    offset_index: usize,
+    scale_index: usize,
+    bit_width_index: usize,
+}
+
+impl QuantizationFormat {
+    fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+        let root = object.borrow();
+
+        Self {
+            num_d: root.get("numD").as_int() as usize,
+            offset_index: root.get("offsetIdx").as_int() as usize,
+            scale_index: root.get("scaleIdx").as_int() as usize,
+            bit_width_index: root.get("bitWidthIdx").as_int() as usize,
+        }
+    }
+}
+
+pub struct HavokDeltaCompressedAnimation {
+    duration: f32,
+    number_of_transform_tracks: usize,
+    number_of_float_tracks: usize,
+    number_of_poses: usize,
+    block_size: usize,
+    quantization_format: QuantizationFormat,
+    quantized_data_index: usize,
+    static_mask_index: usize,
+    static_dofs_index: usize,
+    total_block_size: usize,
+    last_block_size: usize,
+    data: Vec<u8>,
+    annotations: Vec<HavokAnnotationTrack>,
+    extracted_motion: Option<HavokAnimatedReferenceFrame>,
+}
+
+impl HavokDeltaCompressedAnimation {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+        let root = object.borrow();
+
+        let duration = root.get("duration").as_real();
+        let number_of_transform_tracks = root.get("numberOfTransformTracks").as_int() as usize;
+        let number_of_float_tracks = root.find("numberOfFloatTracks").map(|x| x.as_int() as usize).unwrap_or_default();
+        let number_of_poses = root.get("numberOfPoses").as_int() as usize;
+        let block_size = root.get("blockSize").as_int() as usize;
+        let quantization_format = QuantizationFormat::new(root.get("qFormat").as_object());
+        let quantized_data_index = root.get("quantizedDataIdx").as_int() as usize;
+        let static_mask_index = root.get("staticMaskIdx").as_int() as usize;
+        let static_dofs_index = root.get("staticDOFsIdx").as_int() as usize;
+        let total_block_size = root.get("totalBlockSize").as_int() as usize;
+        let last_block_size = root.get("lastBlockSize").as_int() as usize;
+
+        let raw_data = root.get("dataBuffer").as_array();
+        let data = raw_data.iter().map(|x| x.as_int() as u8).collect::<Vec<_>>();
+
+        Self {
+            duration,
+            number_of_transform_tracks,
+            number_of_float_tracks,
+            number_of_poses,
+            block_size,
+            quantization_format,
+            quantized_data_index,
+            static_mask_index,
+            static_dofs_index,
+            total_block_size,
+            last_block_size,
+            data,
+            annotations: HavokAnnotationTrack::read_tracks(&root),
+            extracted_motion: HavokAnimatedReferenceFrame::read(&root),
+        }
+    }
+
+    // each dynamic dof stores the first pose of a block as absolute value, and signed deltas for the rest
+    fn decode_dynamic_dofs(&self, pose: usize) -> Vec<f32> {
+        let format = &self.quantization_format;
+
+        let block = pose / self.block_size;
+        let pose_in_block = pose % self.block_size;
+        let is_last_block = block == (self.number_of_poses.max(1) - 1) / self.block_size;
+        let poses_in_block = if is_last_block { self.last_block_size } else { self.block_size };
+
+        let block_data = &self.data[self.quantized_data_index + block * self.total_block_size..];
+        let mut offsets = ByteReader::new(&self.data[format.offset_index..]);
+        let mut scales = ByteReader::new(&self.data[format.scale_index..]);
+
+        let mut bit_position = 0;
+        (0..format.num_d)
+            .map(|dof| {
+                let bit_width = self.data[format.bit_width_index + dof] as usize;
+                let offset = offsets.read_f32_le();
+                let scale = scales.read_f32_le();
+
+                let mut value = Self::read_bits(block_data, bit_position, bit_width) as i32;
+                for i in 1..=pose_in_block {
+                    let delta = Self::read_bits(block_data, bit_position + i * bit_width, bit_width);
+                    value += Self::sign_extend(delta, bit_width);
+                }
+                bit_position += poses_in_block * bit_width;
+
+                offset + scale * value as f32
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn read_bits(data: &[u8], bit_position: usize, bit_width: usize) -> u32 {
+        (0..bit_width).fold(0, |result, bit| {
+            let position = bit_position + bit;
+            let value = (data[position / 8] >> (position % 8)) & 1;
+
+            result | ((value as u32) << bit)
+        })
+    }
+
+    fn sign_extend(value: u32, bit_width: usize) -> i32 {
+        if bit_width == 0 {
+            return 0;
+        }
+        let shift = 32 - bit_width as u32;
+
+        ((value << shift) as i32) >> shift
+    }
+
+    // float tracks follow transform tracks, one dof each
+    fn decode_pose(&self, pose: usize) -> (Vec<HavokTransform>, Vec<f32>) {
+        let dynamic_dofs = self.decode_dynamic_dofs(pose);
+
+        let mut static_masks = ByteReader::new(&self.data[self.static_mask_index..]);
+        let mut static_dofs = ByteReader::new(&self.data[self.static_dofs_index..]);
+        let mut dynamic_dofs = dynamic_dofs.into_iter();
+
+        let transforms = (0..self.number_of_transform_tracks)
+            .map(|_| {
+                // bit n set means dof n is static
+                let static_mask = static_masks.read_u16_le();
+
+                let mut dofs = [0.; DOFS_PER_TRACK];
+                for (index, dof) in dofs.iter_mut().enumerate() {
+                    *dof = if static_mask & (1 << index) != 0 {
+                        static_dofs.read_f32_le()
+                    } else {
+                        dynamic_dofs.next().unwrap_or_default()
+                    };
+                }
+
+                let rotation = [dofs[3], dofs[4], dofs[5], dofs[6]];
+                let length = rotation.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let rotation = if length > 0. { rotation.map(|x| x / length) } else { [0., 0., 0., 1.] };
+
+                HavokTransform::from_trs([dofs[0], dofs[1], dofs[2], 0.], rotation, [dofs[7], dofs[8], dofs[9], 1.])
+            })
+            .collect::<Vec<_>>();
+
+        let floats = (0..self.number_of_float_tracks)
+            .map(|_| {
+                if static_masks.read_u16_le() & 1 != 0 {
+                    static_dofs.read_f32_le()
+                } else {
+                    dynamic_dofs.next().unwrap_or_default()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        (transforms, floats)
+    }
+
+    fn poses(&self, time: f32) -> (usize, usize, f32) {
+        let (pose, delta) = frame_and_delta(time, self.duration, self.number_of_poses);
+        let next_pose = usize::min(pose + 1, self.number_of_poses.max(1) - 1);
+
+        (pose, next_pose, delta)
+    }
+}
+
+impl HavokAnimation for HavokDeltaCompressedAnimation {
+    fn sample(&self, time: f32) -> Vec<HavokTransform> {
+        let (pose, next_pose, delta) = self.poses(time);
+
+        let (result, _) = self.decode_pose(pose);
+        if next_pose == pose {
+            return result;
+        }
+
+        let (next_result, _) = self.decode_pose(next_pose);
+        result
+            .iter()
+            .zip(next_result.iter())
+            .map(|(a, b)| a.interpolate(b, delta))
+            .collect::<Vec<_>>()
+    }
+
+    fn sample_floats(&self, time: f32) -> Vec<f32> {
+        let (pose, next_pose, delta) = self.poses(time);
+
+        let (_, floats) = self.decode_pose(pose);
+        let (_, next_floats) = self.decode_pose(next_pose);
+
+        floats
+            .iter()
+            .zip(next_floats.iter())
+            .map(|(a, b)| a + (b - a) * delta)
+            .collect::<Vec<_>>()
+    }
+
+    fn annotations(&self) -> &[HavokAnnotationTrack] {
+        &self.annotations
+    }
+
+    fn extracted_motion(&self) -> Option<&HavokAnimatedReferenceFrame> {
+        self.extracted_motion.as_ref()
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+}
@@ -0,0 +1,11 @@
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum HavokError {
+    UnknownSignature,
+    UnsupportedLayout { pointer_size: u8, little_endian: bool },
+    UnsupportedAnimationType(String),
+    TrackCountMismatch { expected: usize, actual: usize },
+}
+
+pub type Result<T> = core::result::Result<T, HavokError>;
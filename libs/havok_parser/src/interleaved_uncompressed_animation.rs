@@ -0,0 +1,115 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::RefCell;
+
+use crate::{
+    animated_reference_frame::HavokAnimatedReferenceFrame,
+    animation::{HavokAnimation, HavokAnnotationTrack},
+    object::HavokObject,
+    transform::HavokTransform,
+};
+
+pub struct HavokInterleavedUncompressedAnimation {
+    duration: f32,
+    number_of_transform_tracks: usize,
+    number_of_float_tracks: usize,
+    transforms: Vec<HavokTransform>,
+    floats: Vec<f32>,
+    annotations: Vec<HavokAnnotationTrack>,
+    extracted_motion: Option<HavokAnimatedReferenceFrame>,
+}
+
+impl HavokInterleavedUncompressedAnimation {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+        let root = object.borrow();
+
+        let duration = root.get("duration").as_real();
+        let number_of_transform_tracks = root.get("numberOfTransformTracks").as_int() as usize;
+
+        let raw_transforms = root.get("transforms").as_array();
+        let transforms = raw_transforms.iter().map(|x| HavokTransform::new(x.as_vec())).collect::<Vec<_>>();
+
+        let number_of_float_tracks = root.find("numberOfFloatTracks").map(|x| x.as_int() as usize).unwrap_or_default();
+        let floats = root
+            .find("floats")
+            .map(|x| x.as_array().iter().map(|x| x.as_real()).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        Self {
+            duration,
+            number_of_transform_tracks,
+            number_of_float_tracks,
+            transforms,
+            floats,
+            annotations: HavokAnnotationTrack::read_tracks(&root),
+            extracted_motion: HavokAnimatedReferenceFrame::read(&root),
+        }
+    }
+
+    // transforms are stored frame by frame, every track of a frame together
+    fn frame(&self, frame: usize) -> &[HavokTransform] {
+        &self.transforms[frame * self.number_of_transform_tracks..(frame + 1) * self.number_of_transform_tracks]
+    }
+}
+
+impl HavokAnimation for HavokInterleavedUncompressedAnimation {
+    fn sample(&self, time: f32) -> Vec<HavokTransform> {
+        if self.number_of_transform_tracks == 0 || self.transforms.is_empty() {
+            return Vec::new();
+        }
+
+        let num_frames = self.transforms.len() / self.number_of_transform_tracks;
+        let (frame, delta) = frame_and_delta(time, self.duration, num_frames);
+
+        let next_frame = usize::min(frame + 1, num_frames - 1);
+
+        self.frame(frame)
+            .iter()
+            .zip(self.frame(next_frame).iter())
+            .map(|(a, b)| a.interpolate(b, delta))
+            .collect::<Vec<_>>()
+    }
+
+    fn sample_floats(&self, time: f32) -> Vec<f32> {
+        if self.number_of_float_tracks == 0 || self.floats.is_empty() {
+            return Vec::new();
+        }
+
+        let num_frames = self.floats.len() / self.number_of_float_tracks;
+        let (frame, delta) = frame_and_delta(time, self.duration, num_frames);
+        let next_frame = usize::min(frame + 1, num_frames - 1);
+
+        (0..self.number_of_float_tracks)
+            .map(|track| {
+                let a = self.floats[frame * self.number_of_float_tracks + track];
+                let b = self.floats[next_frame * self.number_of_float_tracks + track];
+
+                a + (b - a) * delta
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn annotations(&self) -> &[HavokAnnotationTrack] {
+        &self.annotations
+    }
+
+    fn extracted_motion(&self) -> Option<&HavokAnimatedReferenceFrame> {
+        self.extracted_motion.as_ref()
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+// time in milliseconds. returns frame index and fraction to next frame.
+pub(crate) fn frame_and_delta(time: f32, duration: f32, num_frames: usize) -> (usize, f32) {
+    if duration <= 0. || num_frames <= 1 {
+        return (0, 0.);
+    }
+
+    let last_frame = (num_frames - 1) as f32;
+    let frame_float = (((time / 1000.) / duration) * last_frame).clamp(0., last_frame);
+    let frame = frame_float as usize;
+
+    (frame, frame_float - frame as f32)
+}
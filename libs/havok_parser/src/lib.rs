@@ -1,15 +1,38 @@
 extern crate alloc;
 
+mod animated_reference_frame;
 mod animation;
 mod animation_binding;
 mod animation_container;
 mod binary_tag_file_reader;
+mod binary_tag_file_writer;
+mod builder;
 mod byte_reader;
+mod delta_compressed_animation;
+mod error;
+mod interleaved_uncompressed_animation;
 mod object;
+mod packfile_reader;
+mod quantized_animation;
+mod reader;
 mod skeleton;
 mod spline_compressed_animation;
 mod transform;
 
-pub use animation::HavokAnimation;
+pub use animated_reference_frame::HavokAnimatedReferenceFrame;
+pub use animation::{HavokAnimation, HavokAnnotation, HavokAnnotationTrack};
+pub use animation_binding::{HavokAnimationBinding, HavokAnimationBlendHint};
 pub use animation_container::HavokAnimationContainer;
 pub use binary_tag_file_reader::HavokBinaryTagFileReader;
+pub use binary_tag_file_writer::HavokBinaryTagFileWriter;
+pub use builder::HavokAnimationContainerBuilder;
+pub use delta_compressed_animation::HavokDeltaCompressedAnimation;
+pub use error::{HavokError, Result};
+pub use interleaved_uncompressed_animation::HavokInterleavedUncompressedAnimation;
+pub use object::{HavokInteger, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokReal, HavokRootObject, HavokValue, HavokValueType};
+pub use packfile_reader::HavokPackfileReader;
+pub use quantized_animation::HavokQuantizedAnimation;
+pub use reader::HavokReader;
+pub use skeleton::HavokSkeleton;
+pub use spline_compressed_animation::HavokSplineCompressedAnimation;
+pub use transform::HavokTransform;
@@ -143,6 +143,10 @@ impl HavokRootObject {
         Self { object }
     }
 
+    pub fn object(&self) -> Rc<RefCell<HavokObject>> {
+        self.object.clone()
+    }
+
     pub fn find_object_by_type(&self, type_name: &'static str) -> Rc<RefCell<HavokObject>> {
         let root_obj = self.object.borrow();
         let named_variants = root_obj.get("namedVariants");
@@ -194,6 +198,14 @@ impl HavokObjectType {
         }
     }
 
+    pub fn parent(&self) -> Option<&Rc<HavokObjectType>> {
+        self.parent.as_ref()
+    }
+
+    pub fn local_members(&self) -> &[HavokObjectTypeMember] {
+        &self.members
+    }
+
     pub fn member_count(&self) -> usize {
         (if let Some(x) = &self.parent { x.members.len() } else { 0 }) + self.members.len()
     }
@@ -219,6 +231,17 @@ impl HavokObject {
         self.data.get(&member_index).unwrap()
     }
 
+    // for members which may not exist in older versions or partially read structs
+    pub fn find(&self, member_name: &str) -> Option<&HavokValue> {
+        let member_index = self.object_type.members().iter().position(|&x| &*x.name == member_name)?;
+
+        self.data.get(&member_index)
+    }
+
+    pub fn value(&self, index: usize) -> Option<&HavokValue> {
+        self.data.get(&index)
+    }
+
     pub(crate) fn members_mut(&mut self) -> impl Iterator<Item = (&usize, &mut HavokValue)> {
         self.data.iter_mut()
     }
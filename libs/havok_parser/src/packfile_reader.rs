@@ -0,0 +1,535 @@
+use alloc::{borrow::ToOwned, rc::Rc, str, vec::Vec};
+use core::cell::RefCell;
+
+use hashbrown::HashMap;
+use log::debug;
+
+use util::{SliceByteOrderExt, round_up};
+
+use crate::byte_reader::ByteReader;
+use crate::error::{HavokError, Result};
+use crate::object::{HavokObject, HavokObjectType, HavokObjectTypeMember, HavokRootObject, HavokValue, HavokValueType};
+
+#[derive(Clone, Copy)]
+enum ValueKind {
+    U8,
+    I16,
+    I32,
+    U32,
+    Real,
+    String,
+    Pointer(&'static str),
+    Struct(&'static str),
+    QsTransform,
+}
+
+#[derive(Clone, Copy)]
+enum MemberKind {
+    Value(ValueKind),
+    Array(ValueKind),
+}
+
+use MemberKind::{Array, Value};
+use ValueKind::{I16, I32, Pointer, QsTransform, Real, Struct, U8, U32};
+
+struct ClassLayout {
+    name: &'static str,
+    parent: Option<&'static str>,
+    members: &'static [(&'static str, MemberKind)],
+}
+
+// packfiles don't carry reflection data, so layouts of the classes we consume are described here.
+// trailing members we don't use are omitted.
+const CLASS_LAYOUTS: &[ClassLayout] = &[
+    ClassLayout {
+        name: "hkReferencedObject",
+        parent: None,
+        members: &[],
+    },
+    ClassLayout {
+        name: "hkRootLevelContainer",
+        parent: None,
+        members: &[("namedVariants", Array(Struct("hkRootLevelContainerNamedVariant")))],
+    },
+    ClassLayout {
+        name: "hkRootLevelContainerNamedVariant",
+        parent: None,
+        members: &[
+            ("name", Value(ValueKind::String)),
+            ("className", Value(ValueKind::String)),
+            ("variant", Value(Pointer("hkReferencedObject"))),
+        ],
+    },
+    ClassLayout {
+        name: "hkaAnimationContainer",
+        parent: Some("hkReferencedObject"),
+        members: &[
+            ("skeletons", Array(Pointer("hkaSkeleton"))),
+            ("animations", Array(Pointer("hkaAnimation"))),
+            ("bindings", Array(Pointer("hkaAnimationBinding"))),
+            ("attachments", Array(Pointer("hkaBoneAttachment"))),
+            ("skins", Array(Pointer("hkaMeshBinding"))),
+        ],
+    },
+    ClassLayout {
+        name: "hkaSkeleton",
+        parent: Some("hkReferencedObject"),
+        members: &[
+            ("name", Value(ValueKind::String)),
+            ("parentIndices", Array(I16)),
+            ("bones", Array(Struct("hkaBone"))),
+            ("referencePose", Array(QsTransform)),
+            ("referenceFloats", Array(Real)),
+            ("floatSlots", Array(ValueKind::String)),
+        ],
+    },
+    ClassLayout {
+        name: "hkaBone",
+        parent: None,
+        members: &[("name", Value(ValueKind::String)), ("lockTranslation", Value(U8))],
+    },
+    ClassLayout {
+        name: "hkaAnimationBinding",
+        parent: Some("hkReferencedObject"),
+        members: &[
+            ("originalSkeletonName", Value(ValueKind::String)),
+            ("animation", Value(Pointer("hkaAnimation"))),
+            ("transformTrackToBoneIndices", Array(I16)),
+            ("floatTrackToFloatSlotIndices", Array(I16)),
+            ("partitionIndices", Array(I16)),
+            ("blendHint", Value(U8)),
+        ],
+    },
+    ClassLayout {
+        name: "hkaAnimation",
+        parent: Some("hkReferencedObject"),
+        members: &[
+            ("type", Value(I32)),
+            ("duration", Value(Real)),
+            ("numberOfTransformTracks", Value(I32)),
+            ("numberOfFloatTracks", Value(I32)),
+            ("extractedMotion", Value(Pointer("hkaAnimatedReferenceFrame"))),
+            ("annotationTracks", Array(Struct("hkaAnnotationTrack"))),
+        ],
+    },
+    ClassLayout {
+        name: "hkaAnnotationTrack",
+        parent: None,
+        members: &[
+            ("trackName", Value(ValueKind::String)),
+            ("annotations", Array(Struct("hkaAnnotationTrackAnnotation"))),
+        ],
+    },
+    ClassLayout {
+        name: "hkaAnnotationTrackAnnotation",
+        parent: None,
+        members: &[("time", Value(Real)), ("text", Value(ValueKind::String))],
+    },
+    ClassLayout {
+        name: "hkaInterleavedUncompressedAnimation",
+        parent: Some("hkaAnimation"),
+        members: &[("transforms", Array(QsTransform)), ("floats", Array(Real))],
+    },
+    ClassLayout {
+        name: "hkaSplineCompressedAnimation",
+        parent: Some("hkaAnimation"),
+        members: &[
+            ("numFrames", Value(I32)),
+            ("numBlocks", Value(I32)),
+            ("maxFramesPerBlock", Value(I32)),
+            ("maskAndQuantizationSize", Value(I32)),
+            ("blockDuration", Value(Real)),
+            ("blockInverseDuration", Value(Real)),
+            ("frameDuration", Value(Real)),
+            ("blockOffsets", Array(U32)),
+            ("floatBlockOffsets", Array(U32)),
+            ("transformOffsets", Array(U32)),
+            ("floatOffsets", Array(U32)),
+            ("data", Array(U8)),
+            ("endian", Value(I32)),
+        ],
+    },
+    ClassLayout {
+        name: "hkaQuantizedAnimation",
+        parent: Some("hkaAnimation"),
+        members: &[("data", Array(U8)), ("endian", Value(U32))],
+    },
+    ClassLayout {
+        name: "hkaDeltaCompressedAnimation",
+        parent: Some("hkaAnimation"),
+        members: &[
+            ("numberOfPoses", Value(I32)),
+            ("blockSize", Value(I32)),
+            ("qFormat", Value(Struct("hkaDeltaCompressedAnimationQuantizationFormat"))),
+            ("quantizedDataIdx", Value(U32)),
+            ("quantizedDataSize", Value(U32)),
+            ("staticMaskIdx", Value(U32)),
+            ("staticMaskSize", Value(U32)),
+            ("staticDOFsIdx", Value(U32)),
+            ("staticDOFsSize", Value(U32)),
+            ("numStaticTransformDOFs", Value(U32)),
+            ("numDynamicTransformDOFs", Value(U32)),
+            ("totalBlockSize", Value(U32)),
+            ("lastBlockSize", Value(U32)),
+            ("dataBuffer", Array(U8)),
+        ],
+    },
+    ClassLayout {
+        name: "hkaDeltaCompressedAnimationQuantizationFormat",
+        parent: None,
+        members: &[
+            ("maxBitWidth", Value(U8)),
+            ("preserved", Value(U8)),
+            ("numD", Value(U32)),
+            ("offsetIdx", Value(U32)),
+            ("scaleIdx", Value(U32)),
+            ("bitWidthIdx", Value(U32)),
+        ],
+    },
+];
+
+// fixed part of the header, up to the predicate array
+const HEADER_SIZE: usize = 64;
+
+struct Section {
+    data_start: usize,
+}
+
+struct Layout {
+    members: Vec<(usize, MemberKind)>,
+    end: usize,
+    align: usize,
+}
+
+pub struct HavokPackfileReader<'a> {
+    data: &'a [u8],
+    pointer_size: usize,
+    reuse_padding: bool,
+    sections: Vec<Section>,
+    pointers: HashMap<(usize, usize), (usize, usize)>,
+    class_names: HashMap<(usize, usize), Rc<str>>,
+    types: HashMap<Rc<str>, Rc<HavokObjectType>>,
+    objects: HashMap<(usize, usize), Rc<RefCell<HavokObject>>>,
+    null_object: Rc<RefCell<HavokObject>>,
+}
+
+impl<'a> HavokPackfileReader<'a> {
+    pub fn read(data: &'a [u8]) -> Result<HavokRootObject> {
+        if data.len() < HEADER_SIZE {
+            return Err(HavokError::UnknownSignature);
+        }
+        let mut reader = ByteReader::new(data);
+
+        let signature1 = reader.read_bytes(4).to_int_le::<u32>();
+        let signature2 = reader.read_bytes(4).to_int_le::<u32>();
+        if signature1 != 0x57E0_E057 || signature2 != 0x10C0_C010 {
+            return Err(HavokError::UnknownSignature);
+        }
+
+        let _user_tag = reader.read_bytes(4).to_int_le::<u32>();
+        let file_version = reader.read_bytes(4).to_int_le::<u32>();
+        let pointer_size = reader.read() as usize;
+        let little_endian = reader.read();
+        let reuse_padding = reader.read() != 0;
+        let _empty_base_class = reader.read();
+        if little_endian != 1 || (pointer_size != 4 && pointer_size != 8) {
+            return Err(HavokError::UnsupportedLayout {
+                pointer_size: pointer_size as u8,
+                little_endian: little_endian == 1,
+            });
+        }
+
+        let section_count = reader.read_bytes(4).to_int_le::<u32>() as usize;
+        let contents_section = reader.read_bytes(4).to_int_le::<u32>() as usize;
+        let contents_offset = reader.read_bytes(4).to_int_le::<u32>() as usize;
+        let contents_class_name_section = reader.read_bytes(4).to_int_le::<u32>() as usize;
+        let contents_class_name_offset = reader.read_bytes(4).to_int_le::<u32>() as usize;
+        let _contents_version = reader.read_bytes(16);
+        let _flags = reader.read_bytes(4);
+        let max_predicate = reader.read_u16_le() as i16;
+        let predicate_array_size = reader.read_u16_le() as usize;
+
+        if file_version >= 11 && max_predicate != -1 {
+            reader.seek(predicate_array_size);
+        }
+
+        let mut this = Self {
+            data,
+            pointer_size,
+            reuse_padding,
+            sections: Vec::with_capacity(section_count),
+            pointers: HashMap::new(),
+            class_names: HashMap::new(),
+            types: HashMap::new(),
+            objects: HashMap::new(),
+            null_object: Rc::new(RefCell::new(HavokObject::new(
+                Rc::new(HavokObjectType::new(Rc::from("object"), None, Vec::new())),
+                HashMap::new(),
+            ))),
+        };
+
+        let mut section_fixups = Vec::with_capacity(section_count);
+        for _ in 0..section_count {
+            let _tag = reader.read_bytes(20);
+            let data_start = reader.read_bytes(4).to_int_le::<u32>() as usize;
+            let fixups = (0..6).map(|_| reader.read_bytes(4).to_int_le::<u32>() as usize).collect::<Vec<_>>();
+            if file_version >= 11 {
+                reader.seek(16);
+            }
+
+            this.sections.push(Section { data_start });
+            section_fixups.push(fixups);
+        }
+
+        for (index, fixups) in section_fixups.iter().enumerate() {
+            // local, global, virtual, exports, imports, end
+            this.read_fixups(index, fixups[0], fixups[1], fixups[2], fixups[3]);
+        }
+
+        let root_class_name = this.read_c_string(contents_class_name_section, contents_class_name_offset);
+        let root = this.read_object(contents_section, contents_offset, root_class_name);
+
+        Ok(HavokRootObject::new(root))
+    }
+
+    fn read_fixups(&mut self, section: usize, local: usize, global: usize, virtual_: usize, exports: usize) {
+        let data_start = self.sections[section].data_start;
+        let read_u32 = |offset: usize| {
+            let data = &self.data[data_start + offset..];
+            data.to_int_le::<u32>()
+        };
+
+        let mut pointers = Vec::new();
+        let mut class_names = Vec::new();
+
+        for offset in (local..global).step_by(8) {
+            let source = read_u32(offset);
+            if source != 0xFFFF_FFFF {
+                pointers.push(((section, source as usize), (section, read_u32(offset + 4) as usize)));
+            }
+        }
+
+        for offset in (global..virtual_).step_by(12) {
+            let source = read_u32(offset);
+            if source != 0xFFFF_FFFF {
+                pointers.push(((section, source as usize), (read_u32(offset + 4) as usize, read_u32(offset + 8) as usize)));
+            }
+        }
+
+        for offset in (virtual_..exports).step_by(12) {
+            let source = read_u32(offset);
+            if source != 0xFFFF_FFFF {
+                class_names.push(((section, source as usize), (read_u32(offset + 4) as usize, read_u32(offset + 8) as usize)));
+            }
+        }
+
+        self.pointers.extend(pointers);
+        for (object, (name_section, name_offset)) in class_names {
+            let class_name = self.read_c_string(name_section, name_offset);
+            self.class_names.insert(object, class_name);
+        }
+    }
+
+    fn read_c_string(&self, section: usize, offset: usize) -> Rc<str> {
+        let data = &self.data[self.sections[section].data_start + offset..];
+        let end = data.iter().position(|&x| x == 0).unwrap();
+
+        Rc::from(str::from_utf8(&data[..end]).unwrap().to_owned())
+    }
+
+    fn find_class(name: &str) -> Option<&'static ClassLayout> {
+        CLASS_LAYOUTS.iter().find(|x| x.name == name)
+    }
+
+    fn value_size_align(&self, kind: ValueKind) -> (usize, usize) {
+        match kind {
+            U8 => (1, 1),
+            I16 => (2, 2),
+            I32 | U32 | Real => (4, 4),
+            ValueKind::String | Pointer(_) => (self.pointer_size, self.pointer_size),
+            Struct(name) => {
+                let layout = self.layout(Self::find_class(name).unwrap());
+                (round_up(layout.end, layout.align), layout.align)
+            }
+            QsTransform => (48, 16),
+        }
+    }
+
+    fn member_size_align(&self, kind: MemberKind) -> (usize, usize) {
+        match kind {
+            Value(kind) => self.value_size_align(kind),
+            // pointer, size, capacity and flags
+            Array(_) => (self.pointer_size + 8, self.pointer_size),
+        }
+    }
+
+    fn layout(&self, class: &ClassLayout) -> Layout {
+        // vtable, memSizeAndFlags, referenceCount
+        if class.name == "hkReferencedObject" {
+            return Layout {
+                members: Vec::new(),
+                end: self.pointer_size + 4,
+                align: self.pointer_size,
+            };
+        }
+
+        let mut layout = if let Some(parent) = class.parent {
+            let mut layout = self.layout(Self::find_class(parent).unwrap());
+            if !self.reuse_padding {
+                layout.end = round_up(layout.end, layout.align);
+            }
+            layout
+        } else {
+            Layout {
+                members: Vec::new(),
+                end: 0,
+                align: 1,
+            }
+        };
+
+        for &(_, kind) in class.members {
+            let (size, align) = self.member_size_align(kind);
+            let offset = round_up(layout.end, align);
+
+            layout.members.push((offset, kind));
+            layout.end = offset + size;
+            layout.align = usize::max(layout.align, align);
+        }
+
+        layout
+    }
+
+    fn object_type(&mut self, name: &str) -> Rc<HavokObjectType> {
+        if let Some(x) = self.types.get(name) {
+            return x.clone();
+        }
+
+        let (parent, members) = if let Some(class) = Self::find_class(name) {
+            let parent = class.parent.map(|x| self.object_type(x));
+            let members = class
+                .members
+                .iter()
+                .map(|&(name, kind)| {
+                    let (type_, class_name) = match kind {
+                        Value(x) => Self::value_type(x),
+                        Array(x) => {
+                            let (type_, class_name) = Self::value_type(x);
+                            (type_ | HavokValueType::ARRAY, class_name)
+                        }
+                    };
+
+                    HavokObjectTypeMember::new(Rc::from(name), type_, 0, class_name.map(Rc::from))
+                })
+                .collect::<Vec<_>>();
+
+            (parent, members)
+        } else {
+            debug!("Unknown packfile class {}", name);
+
+            (None, Vec::new())
+        };
+
+        let object_type = Rc::new(HavokObjectType::new(Rc::from(name), parent, members));
+        self.types.insert(Rc::from(name), object_type.clone());
+
+        object_type
+    }
+
+    fn value_type(kind: ValueKind) -> (HavokValueType, Option<&'static str>) {
+        match kind {
+            U8 => (HavokValueType::BYTE, None),
+            I16 | I32 | U32 => (HavokValueType::INT, None),
+            Real => (HavokValueType::REAL, None),
+            ValueKind::String => (HavokValueType::STRING, None),
+            Pointer(x) => (HavokValueType::OBJECT, Some(x)),
+            Struct(x) => (HavokValueType::STRUCT, Some(x)),
+            QsTransform => (HavokValueType::VEC12, None),
+        }
+    }
+
+    fn read_object(&mut self, section: usize, offset: usize, class_name: Rc<str>) -> Rc<RefCell<HavokObject>> {
+        if let Some(x) = self.objects.get(&(section, offset)) {
+            return x.clone();
+        }
+
+        let object = Rc::new(RefCell::new(HavokObject::new(self.object_type(&class_name), HashMap::new())));
+
+        // register before reading members to allow cyclic references
+        self.objects.insert((section, offset), object.clone());
+        self.read_members(&object, section, offset, &class_name);
+
+        object
+    }
+
+    // structs are stored inline, so they can share an offset with their container
+    fn read_struct(&mut self, section: usize, offset: usize, class_name: &str) -> Rc<RefCell<HavokObject>> {
+        let object = Rc::new(RefCell::new(HavokObject::new(self.object_type(class_name), HashMap::new())));
+        self.read_members(&object, section, offset, class_name);
+
+        object
+    }
+
+    fn read_members(&mut self, object: &Rc<RefCell<HavokObject>>, section: usize, offset: usize, class_name: &str) {
+        if let Some(class) = Self::find_class(class_name) {
+            let layout = self.layout(class);
+            for (index, (member_offset, kind)) in layout.members.into_iter().enumerate() {
+                let value = self.read_member(section, offset + member_offset, kind);
+                object.borrow_mut().set(index, value);
+            }
+        }
+    }
+
+    fn read_member(&mut self, section: usize, offset: usize, kind: MemberKind) -> HavokValue {
+        match kind {
+            Value(x) => self.read_value(section, offset, x),
+            Array(x) => {
+                let size_data = &self.data[self.sections[section].data_start + offset + self.pointer_size..];
+                let count = size_data.to_int_le::<u32>() as usize;
+                let (stride, _) = self.value_size_align(x);
+
+                let values = if let Some(&(target_section, target_offset)) = self.pointers.get(&(section, offset)) {
+                    (0..count)
+                        .map(|index| self.read_value(target_section, target_offset + index * stride, x))
+                        .collect::<Vec<_>>()
+                } else {
+                    Vec::new()
+                };
+
+                HavokValue::Array(values)
+            }
+        }
+    }
+
+    fn read_value(&mut self, section: usize, offset: usize, kind: ValueKind) -> HavokValue {
+        let data = &self.data[self.sections[section].data_start + offset..];
+
+        match kind {
+            U8 => HavokValue::Integer(data[0] as i32),
+            I16 => HavokValue::Integer(data.to_int_le::<i16>() as i32),
+            I32 => HavokValue::Integer(data.to_int_le::<i32>()),
+            U32 => HavokValue::Integer(data.to_int_le::<u32>() as i32),
+            Real => HavokValue::Real(ByteReader::new(data).read_f32_le()),
+            QsTransform => {
+                let mut reader = ByteReader::new(data);
+                HavokValue::Vec((0..12).map(|_| reader.read_f32_le()).collect::<Vec<_>>())
+            }
+            ValueKind::String => match self.pointers.get(&(section, offset)) {
+                Some(&(target_section, target_offset)) => HavokValue::String(self.read_c_string(target_section, target_offset)),
+                None => HavokValue::String(Rc::from("")),
+            },
+            Pointer(_) => match self.pointers.get(&(section, offset)).copied() {
+                Some((target_section, target_offset)) => {
+                    let class_name = self
+                        .class_names
+                        .get(&(target_section, target_offset))
+                        .cloned()
+                        .unwrap_or_else(|| Rc::from("hkReferencedObject"));
+
+                    HavokValue::Object(self.read_object(target_section, target_offset, class_name))
+                }
+                None => HavokValue::Object(self.null_object.clone()),
+            },
+            Struct(name) => HavokValue::Object(self.read_struct(section, offset, name)),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use alloc::{rc::Rc, vec, vec::Vec};
+use core::cell::RefCell;
+
+use log::warn;
+
+use crate::{
+    animated_reference_frame::HavokAnimatedReferenceFrame,
+    animation::{HavokAnimation, HavokAnnotationTrack},
+    byte_reader::ByteReader,
+    interleaved_uncompressed_animation::frame_and_delta,
+    object::HavokObject,
+    spline_compressed_animation::HavokSplineCompressedAnimation,
+    transform::HavokTransform,
+};
+
+// elements index the 12 floats of each bone's hkQsTransform
+const FLOATS_PER_TRANSFORM: usize = 12;
+const ROTATION_OFFSET: usize = 4;
+const SCALE_OFFSET: usize = 8;
+const BYTES_PER_ROTATION: usize = 6;
+
+struct QuantizedAnimationHeader {
+    num_bones: usize,
+    num_floats: usize,
+    frame_size: usize,
+    num_static_translations: usize,
+    num_static_rotations: usize,
+    num_static_scales: usize,
+    num_static_floats: usize,
+    num_dynamic_translations: usize,
+    num_dynamic_rotations: usize,
+    num_dynamic_scales: usize,
+    num_dynamic_floats: usize,
+    static_elements_offset: usize,
+    static_values_offset: usize,
+    dynamic_elements_offset: usize,
+    dynamic_range_minimums_offset: usize,
+    dynamic_range_spans_offset: usize,
+    dynamic_values_offset: usize,
+    num_frames: usize,
+}
+
+impl QuantizedAnimationHeader {
+    fn read(data: &[u8]) -> Self {
+        let mut reader = ByteReader::new(data);
+
+        let _header_size = reader.read_u16_le();
+        let num_bones = reader.read_u16_le() as usize;
+        let num_floats = reader.read_u16_le() as usize;
+        let frame_size = reader.read_u16_le() as usize;
+        let num_static_translations = reader.read_u16_le() as usize;
+        let num_static_rotations = reader.read_u16_le() as usize;
+        let num_static_scales = reader.read_u16_le() as usize;
+        let num_static_floats = reader.read_u16_le() as usize;
+        let num_dynamic_translations = reader.read_u16_le() as usize;
+        let num_dynamic_rotations = reader.read_u16_le() as usize;
+        let num_dynamic_scales = reader.read_u16_le() as usize;
+        let num_dynamic_floats = reader.read_u16_le() as usize;
+        let static_elements_offset = reader.read_u16_le() as usize;
+        let static_values_offset = reader.read_u16_le() as usize;
+        let dynamic_elements_offset = reader.read_u16_le() as usize;
+        let dynamic_range_minimums_offset = reader.read_u16_le() as usize;
+        let dynamic_range_spans_offset = reader.read_u16_le() as usize;
+        let dynamic_values_offset = reader.read_u16_le() as usize;
+        let _frame_duration = reader.read_f32_le();
+        let num_frames = reader.read_u32_le() as usize;
+
+        Self {
+            num_bones,
+            num_floats,
+            frame_size,
+            num_static_translations,
+            num_static_rotations,
+            num_static_scales,
+            num_static_floats,
+            num_dynamic_translations,
+            num_dynamic_rotations,
+            num_dynamic_scales,
+            num_dynamic_floats,
+            static_elements_offset,
+            static_values_offset,
+            dynamic_elements_offset,
+            dynamic_range_minimums_offset,
+            dynamic_range_spans_offset,
+            dynamic_values_offset,
+            num_frames,
+        }
+    }
+}
+
+pub struct HavokQuantizedAnimation {
+    duration: f32,
+    header: QuantizedAnimationHeader,
+    data: Vec<u8>,
+    annotations: Vec<HavokAnnotationTrack>,
+    extracted_motion: Option<HavokAnimatedReferenceFrame>,
+}
+
+impl HavokQuantizedAnimation {
+    pub fn new(object: Rc<RefCell<HavokObject>>) -> Self {
+        let root = object.borrow();
+
+        let duration = root.get("duration").as_real();
+
+        let raw_data = root.get("data").as_array();
+        let data = raw_data.iter().map(|x| x.as_int() as u8).collect::<Vec<_>>();
+
+        let header = QuantizedAnimationHeader::read(&data);
+
+        Self {
+            duration,
+            header,
+            data,
+            annotations: HavokAnnotationTrack::read_tracks(&root),
+            extracted_motion: HavokAnimatedReferenceFrame::read(&root),
+        }
+    }
+
+    fn read_elements(&self, offset: usize, count: usize) -> Vec<usize> {
+        let mut reader = ByteReader::new(&self.data[offset..]);
+
+        (0..count).map(|_| reader.read_u16_le() as usize).collect::<Vec<_>>()
+    }
+
+    fn read_floats(&self, offset: usize, count: usize) -> Vec<f32> {
+        let mut reader = ByteReader::new(&self.data[offset..]);
+
+        (0..count).map(|_| reader.read_f32_le()).collect::<Vec<_>>()
+    }
+
+    fn apply_scalar(pose: &mut [HavokTransform], element: usize, value: f32) {
+        let component = element % FLOATS_PER_TRANSFORM;
+        let Some(transform) = pose.get_mut(element / FLOATS_PER_TRANSFORM) else {
+            warn!("Quantized element {element} is out of range");
+            return;
+        };
+
+        match component {
+            0..ROTATION_OFFSET => transform.translation[component] = value,
+            SCALE_OFFSET.. => transform.scale[component - SCALE_OFFSET] = value,
+            _ => warn!("Quantized scalar element {element} targets rotation"),
+        }
+    }
+
+    fn apply_rotation(pose: &mut [HavokTransform], element: usize, value: [f32; 4]) {
+        match pose.get_mut(element / FLOATS_PER_TRANSFORM) {
+            Some(transform) => transform.rotation = value,
+            None => warn!("Quantized element {element} is out of range"),
+        }
+    }
+
+    fn apply_float(floats: &mut [f32], element: usize, value: f32) {
+        match floats.get_mut(element) {
+            Some(float) => *float = value,
+            None => warn!("Quantized float element {element} is out of range"),
+        }
+    }
+
+    // float elements are float track indices
+    fn apply_static(&self, pose: &mut [HavokTransform], floats: &mut [f32]) {
+        let header = &self.header;
+        let elements = self.read_elements(
+            header.static_elements_offset,
+            header.num_static_translations + header.num_static_rotations + header.num_static_scales + header.num_static_floats,
+        );
+        let (translation_elements, rest) = elements.split_at(header.num_static_translations);
+        let (rotation_elements, rest) = rest.split_at(header.num_static_rotations);
+        let (scale_elements, float_elements) = rest.split_at(header.num_static_scales);
+
+        let mut values = ByteReader::new(&self.data[header.static_values_offset..]);
+        for &element in translation_elements {
+            Self::apply_scalar(pose, element, values.read_f32_le());
+        }
+        for &element in rotation_elements {
+            let rotation = HavokSplineCompressedAnimation::unpack_signed_quaternion_48(values.read_bytes(BYTES_PER_ROTATION));
+            Self::apply_rotation(pose, element, rotation);
+        }
+        for &element in scale_elements {
+            Self::apply_scalar(pose, element, values.read_f32_le());
+        }
+        for &element in float_elements {
+            Self::apply_float(floats, element, values.read_f32_le());
+        }
+    }
+
+    fn apply_dynamic(&self, pose: &mut [HavokTransform], floats: &mut [f32], frame: usize) {
+        let header = &self.header;
+        let elements = self.read_elements(
+            header.dynamic_elements_offset,
+            header.num_dynamic_translations + header.num_dynamic_rotations + header.num_dynamic_scales + header.num_dynamic_floats,
+        );
+        let (translation_elements, rest) = elements.split_at(header.num_dynamic_translations);
+        let (rotation_elements, rest) = rest.split_at(header.num_dynamic_rotations);
+        let (scale_elements, float_elements) = rest.split_at(header.num_dynamic_scales);
+
+        // ranges only exist for scalar elements
+        let scalar_count = header.num_dynamic_translations + header.num_dynamic_scales + header.num_dynamic_floats;
+        let minimums = self.read_floats(header.dynamic_range_minimums_offset, scalar_count);
+        let spans = self.read_floats(header.dynamic_range_spans_offset, scalar_count);
+        let dequantize = |index: usize, value: u16| minimums[index] + spans[index] * (value as f32 / u16::MAX as f32);
+
+        let mut values = ByteReader::new(&self.data[header.dynamic_values_offset + frame * header.frame_size..]);
+        for (index, &element) in translation_elements.iter().enumerate() {
+            Self::apply_scalar(pose, element, dequantize(index, values.read_u16_le()));
+        }
+        for &element in rotation_elements {
+            let rotation = HavokSplineCompressedAnimation::unpack_signed_quaternion_48(values.read_bytes(BYTES_PER_ROTATION));
+            Self::apply_rotation(pose, element, rotation);
+        }
+        for (index, &element) in scale_elements.iter().enumerate() {
+            Self::apply_scalar(pose, element, dequantize(header.num_dynamic_translations + index, values.read_u16_le()));
+        }
+        let float_range_offset = header.num_dynamic_translations + header.num_dynamic_scales;
+        for (index, &element) in float_elements.iter().enumerate() {
+            Self::apply_float(floats, element, dequantize(float_range_offset + index, values.read_u16_le()));
+        }
+    }
+
+    fn decode_frame(&self, frame: usize) -> (Vec<HavokTransform>, Vec<f32>) {
+        let mut pose = vec![HavokTransform::identity(); self.header.num_bones];
+        let mut floats = vec![0.; self.header.num_floats];
+
+        self.apply_static(&mut pose, &mut floats);
+        self.apply_dynamic(&mut pose, &mut floats, frame);
+
+        (pose, floats)
+    }
+
+    fn frames(&self, time: f32) -> (usize, usize, f32) {
+        let (frame, delta) = frame_and_delta(time, self.duration, self.header.num_frames);
+        let next_frame = usize::min(frame + 1, self.header.num_frames.max(1) - 1);
+
+        (frame, next_frame, delta)
+    }
+}
+
+impl HavokAnimation for HavokQuantizedAnimation {
+    fn sample(&self, time: f32) -> Vec<HavokTransform> {
+        let (frame, next_frame, delta) = self.frames(time);
+
+        let (pose, _) = self.decode_frame(frame);
+        if next_frame == frame {
+            return pose;
+        }
+
+        let (next_pose, _) = self.decode_frame(next_frame);
+        pose.iter()
+            .zip(next_pose.iter())
+            .map(|(a, b)| a.interpolate(b, delta))
+            .collect::<Vec<_>>()
+    }
+
+    fn sample_floats(&self, time: f32) -> Vec<f32> {
+        let (frame, next_frame, delta) = self.frames(time);
+
+        let (_, floats) = self.decode_frame(frame);
+        let (_, next_floats) = self.decode_frame(next_frame);
+
+        floats
+            .iter()
+            .zip(next_floats.iter())
+            .map(|(a, b)| a + (b - a) * delta)
+            .collect::<Vec<_>>()
+    }
+
+    fn annotations(&self) -> &[HavokAnnotationTrack] {
+        &self.annotations
+    }
+
+    fn extracted_motion(&self) -> Option<&HavokAnimatedReferenceFrame> {
+        self.extracted_motion.as_ref()
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+}
@@ -0,0 +1,31 @@
+use util::SliceByteOrderExt;
+
+use crate::{
+    binary_tag_file_reader::HavokBinaryTagFileReader,
+    error::{HavokError, Result},
+    object::HavokRootObject,
+    packfile_reader::HavokPackfileReader,
+};
+
+const TAGFILE_SIGNATURE: [u32; 2] = [0xCAB0_0D1E, 0xD011_FACE];
+const PACKFILE_SIGNATURE: [u32; 2] = [0x57E0_E057, 0x10C0_C010];
+
+// reads either binary tagfile or packfile, by signature
+pub struct HavokReader {}
+
+impl HavokReader {
+    pub fn read(data: &[u8]) -> Result<HavokRootObject> {
+        if data.len() < 8 {
+            return Err(HavokError::UnknownSignature);
+        }
+
+        let signature = [(&data[0..4]).to_int_le::<u32>(), (&data[4..8]).to_int_le::<u32>()];
+        if signature == TAGFILE_SIGNATURE {
+            Ok(HavokBinaryTagFileReader::read(data))
+        } else if signature == PACKFILE_SIGNATURE {
+            HavokPackfileReader::read(data)
+        } else {
+            Err(HavokError::UnknownSignature)
+        }
+    }
+}
@@ -35,4 +35,39 @@ impl HavokSkeleton {
             reference_pose,
         }
     }
+
+    // parents always come before their children
+    pub fn to_model_space(&self, local_pose: &[HavokTransform]) -> Vec<HavokTransform> {
+        let mut result: Vec<HavokTransform> = Vec::with_capacity(local_pose.len());
+
+        for (index, local) in local_pose.iter().enumerate() {
+            let parent_index = self.parent_indices[index];
+            if parent_index < index {
+                result.push(result[parent_index].multiply(local));
+            } else {
+                result.push(local.clone());
+            }
+        }
+
+        result
+    }
+
+    // appends bones of partial skeleton which doesn't exist in this skeleton, connecting them by parent bone name
+    pub fn merge(&mut self, other: &HavokSkeleton) {
+        for (index, bone_name) in other.bone_names.iter().enumerate() {
+            if self.bone_names.contains(bone_name) {
+                continue;
+            }
+
+            let parent_index = other
+                .bone_names
+                .get(other.parent_indices[index])
+                .and_then(|parent_name| self.bone_names.iter().position(|x| x == parent_name))
+                .unwrap_or(usize::MAX);
+
+            self.bone_names.push(bone_name.clone());
+            self.parent_indices.push(parent_index);
+            self.reference_pose.push(other.reference_pose[index].clone());
+        }
+    }
 }
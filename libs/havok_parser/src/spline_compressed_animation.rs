@@ -3,7 +3,12 @@ use core::{cell::RefCell, cmp};
 use std::f32;
 
 use crate::byte_reader::ByteReader;
-use crate::{animation::HavokAnimation, object::HavokObject, transform::HavokTransform};
+use crate::{
+    animated_reference_frame::HavokAnimatedReferenceFrame,
+    animation::{HavokAnimation, HavokAnnotationTrack},
+    object::HavokObject,
+    transform::HavokTransform,
+};
 
 #[repr(u8)]
 #[allow(clippy::upper_case_acronyms)]
@@ -79,6 +84,7 @@ impl ScalarQuantization {
 pub struct HavokSplineCompressedAnimation {
     duration: f32,
     number_of_transform_tracks: usize,
+    number_of_float_tracks: usize,
     num_frames: usize,
     num_blocks: usize,
     max_frames_per_block: usize,
@@ -86,7 +92,10 @@ pub struct HavokSplineCompressedAnimation {
     block_inverse_duration: f32,
     frame_duration: f32,
     block_offsets: Vec<u32>,
+    float_block_offsets: Vec<u32>,
     data: Vec<u8>,
+    annotations: Vec<HavokAnnotationTrack>,
+    extracted_motion: Option<HavokAnimatedReferenceFrame>,
 }
 
 impl HavokSplineCompressedAnimation {
@@ -95,6 +104,7 @@ impl HavokSplineCompressedAnimation {
 
         let duration = root.get("duration").as_real();
         let number_of_transform_tracks = root.get("numberOfTransformTracks").as_int() as usize;
+        let number_of_float_tracks = root.find("numberOfFloatTracks").map(|x| x.as_int() as usize).unwrap_or_default();
         let num_frames = root.get("numFrames").as_int() as usize;
         let num_blocks = root.get("numBlocks").as_int() as usize;
         let max_frames_per_block = root.get("maxFramesPerBlock").as_int() as usize;
@@ -105,12 +115,18 @@ impl HavokSplineCompressedAnimation {
         let raw_block_offsets = root.get("blockOffsets").as_array();
         let block_offsets = raw_block_offsets.iter().map(|x| x.as_int() as u32).collect::<Vec<_>>();
 
+        let float_block_offsets = root
+            .find("floatBlockOffsets")
+            .map(|x| x.as_array().iter().map(|x| x.as_int() as u32).collect::<Vec<_>>())
+            .unwrap_or_default();
+
         let raw_data = root.get("data").as_array();
         let data = raw_data.iter().map(|x| x.as_int() as u8).collect::<Vec<_>>();
 
         Self {
             duration,
             number_of_transform_tracks,
+            number_of_float_tracks,
             num_frames,
             num_blocks,
             max_frames_per_block,
@@ -118,10 +134,20 @@ impl HavokSplineCompressedAnimation {
             block_inverse_duration,
             frame_duration,
             block_offsets,
+            float_block_offsets,
             data,
+            annotations: HavokAnnotationTrack::read_tracks(&root),
+            extracted_motion: HavokAnimatedReferenceFrame::read(&root),
         }
     }
 
+    fn frame_and_delta(&self, time: f32) -> (usize, f32) {
+        let frame_float = ((time / 1000.) / self.duration) * (self.num_frames as f32 - 1.);
+        let frame = frame_float as usize;
+
+        (frame, frame_float - frame as f32)
+    }
+
     fn get_block_and_time(&self, frame: usize, delta: f32) -> (usize, f32, u8) {
         let mut block_out = frame / (self.max_frames_per_block - 1);
 
@@ -261,7 +287,7 @@ impl HavokSplineCompressedAnimation {
         }
     }
 
-    fn unpack_signed_quaternion_48(data: &[u8]) -> [f32; 4] {
+    pub(crate) fn unpack_signed_quaternion_48(data: &[u8]) -> [f32; 4] {
         let data = [
             u16::from_le_bytes([data[0], data[1]]),
             u16::from_le_bytes([data[2], data[3]]),
@@ -555,9 +581,7 @@ impl HavokSplineCompressedAnimation {
 
 impl HavokAnimation for HavokSplineCompressedAnimation {
     fn sample(&self, time: f32) -> Vec<HavokTransform> {
-        let frame_float = ((time / 1000.) / self.duration) * (self.num_frames as f32 - 1.);
-        let frame = frame_float as usize;
-        let delta = frame_float - frame as f32;
+        let (frame, delta) = self.frame_and_delta(time);
 
         let (block, block_time, quantized_time) = self.get_block_and_time(frame, delta);
 
@@ -585,6 +609,51 @@ impl HavokAnimation for HavokSplineCompressedAnimation {
         result
     }
 
+    // float masks follow transform masks, and float data starts at float block offset.
+    // float tracks are always quantized to 16 bits.
+    fn sample_floats(&self, time: f32) -> Vec<f32> {
+        if self.number_of_float_tracks == 0 || self.float_block_offsets.is_empty() {
+            return Vec::new();
+        }
+
+        let (frame, delta) = self.frame_and_delta(time);
+        let (block, block_time, quantized_time) = self.get_block_and_time(frame, delta);
+
+        let block_data = Self::compute_packed_nurbs_offsets(&self.data, &self.block_offsets, block, 0x8000_0000);
+        let mut mask = ByteReader::new(&block_data[self.number_of_transform_tracks * 4..]);
+        let mut data = ByteReader::new(&block_data[self.float_block_offsets[block] as usize..]);
+
+        (0..self.number_of_float_tracks)
+            .map(|_| {
+                let mask = mask.read();
+                let result = if mask != 0 {
+                    Self::read_nurbs_curve(
+                        ScalarQuantization::BITS16,
+                        &mut data,
+                        quantized_time,
+                        self.frame_duration,
+                        block_time,
+                        mask,
+                        [0., 0., 0., 0.],
+                    )[0]
+                } else {
+                    0.
+                };
+                data.align(4);
+
+                result
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn annotations(&self) -> &[HavokAnnotationTrack] {
+        &self.annotations
+    }
+
+    fn extracted_motion(&self) -> Option<&HavokAnimatedReferenceFrame> {
+        self.extracted_motion.as_ref()
+    }
+
     fn duration(&self) -> f32 {
         self.duration
     }
@@ -1,5 +1,8 @@
+use alloc::vec::Vec;
+
 use crate::object::HavokReal;
 
+#[derive(Clone)]
 pub struct HavokTransform {
     pub translation: [f32; 4],
     pub rotation: [f32; 4],
@@ -22,4 +25,138 @@ impl HavokTransform {
             scale,
         }
     }
+
+    pub fn identity() -> Self {
+        Self::from_trs([0., 0., 0., 0.], [0., 0., 0., 1.], [1., 1., 1., 1.])
+    }
+
+    // linear translation and scale, normalized linear rotation
+    pub fn interpolate(&self, other: &Self, t: f32) -> Self {
+        let lerp = |a: [f32; 4], b: [f32; 4]| [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t);
+
+        let dot = Self::dot(self.rotation, other.rotation);
+        let other_rotation = if dot < 0. { other.rotation.map(|x| -x) } else { other.rotation };
+
+        let rotation = lerp(self.rotation, other_rotation);
+        let length = rotation.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+        Self {
+            translation: lerp(self.translation, other.translation),
+            rotation: rotation.map(|x| x / length),
+            scale: lerp(self.scale, other.scale),
+        }
+    }
+
+    // linear translation and scale, spherical linear rotation
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let dot = Self::dot(self.rotation, other.rotation);
+        let (other_rotation, dot) = if dot < 0. {
+            (other.rotation.map(|x| -x), -dot)
+        } else {
+            (other.rotation, dot)
+        };
+
+        // fall back to normalized lerp on nearly parallel rotations
+        if dot > 0.9995 {
+            return self.interpolate(&Self::from_trs(other.translation, other_rotation, other.scale), t);
+        }
+
+        let theta = dot.acos();
+        let sin_theta = theta.sin();
+        let a = ((1. - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+
+        let lerp = |a: [f32; 4], b: [f32; 4]| [0, 1, 2, 3].map(|i| a[i] + (b[i] - a[i]) * t);
+
+        Self {
+            translation: lerp(self.translation, other.translation),
+            rotation: [0, 1, 2, 3].map(|i| self.rotation[i] * a + other_rotation[i] * b),
+            scale: lerp(self.scale, other.scale),
+        }
+    }
+
+    // self is parent of child
+    pub fn multiply(&self, child: &Self) -> Self {
+        let scaled = [0, 1, 2].map(|i| self.scale[i] * child.translation[i]);
+        let rotated = Self::rotate(self.rotation, scaled);
+
+        Self {
+            translation: [
+                self.translation[0] + rotated[0],
+                self.translation[1] + rotated[1],
+                self.translation[2] + rotated[2],
+                0.,
+            ],
+            rotation: Self::quaternion_multiply(self.rotation, child.rotation),
+            scale: [0, 1, 2, 3].map(|i| self.scale[i] * child.scale[i]),
+        }
+    }
+
+    // additive transform holds difference from reference pose
+    pub fn apply_additive(&self, additive: &Self, weight: f32) -> Self {
+        let additive = Self::identity().slerp(additive, weight);
+
+        Self {
+            translation: [0, 1, 2, 3].map(|i| self.translation[i] + additive.translation[i]),
+            rotation: Self::quaternion_multiply(self.rotation, additive.rotation),
+            scale: [0, 1, 2, 3].map(|i| self.scale[i] * additive.scale[i]),
+        }
+    }
+
+    // column major 4x4 matrix
+    pub fn to_matrix(&self) -> [f32; 16] {
+        let [x, y, z, w] = self.rotation;
+        let [sx, sy, sz, _] = self.scale;
+        let [tx, ty, tz, _] = self.translation;
+
+        [
+            (1. - 2. * (y * y + z * z)) * sx,
+            (2. * (x * y + z * w)) * sx,
+            (2. * (x * z - y * w)) * sx,
+            0.,
+            (2. * (x * y - z * w)) * sy,
+            (1. - 2. * (x * x + z * z)) * sy,
+            (2. * (y * z + x * w)) * sy,
+            0.,
+            (2. * (x * z + y * w)) * sz,
+            (2. * (y * z - x * w)) * sz,
+            (1. - 2. * (x * x + y * y)) * sz,
+            0.,
+            tx,
+            ty,
+            tz,
+            1.,
+        ]
+    }
+
+    // weight 0 is a, weight 1 is b
+    pub fn blend_poses(a: &[Self], b: &[Self], weight: f32) -> Vec<Self> {
+        a.iter().zip(b.iter()).map(|(a, b)| a.slerp(b, weight)).collect::<Vec<_>>()
+    }
+
+    fn dot(a: [f32; 4], b: [f32; 4]) -> f32 {
+        (0..4).map(|i| a[i] * b[i]).sum::<f32>()
+    }
+
+    fn quaternion_multiply(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+        let [ax, ay, az, aw] = a;
+        let [bx, by, bz, bw] = b;
+
+        [
+            aw * bx + ax * bw + ay * bz - az * by,
+            aw * by - ax * bz + ay * bw + az * bx,
+            aw * bz + ax * by - ay * bx + az * bw,
+            aw * bw - ax * bx - ay * by - az * bz,
+        ]
+    }
+
+    fn rotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+        let cross = |a: [f32; 3], b: [f32; 3]| [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+
+        let u = [q[0], q[1], q[2]];
+        let t = cross(u, v).map(|x| x * 2.);
+        let c = cross(u, t);
+
+        [0, 1, 2].map(|i| v[i] + q[3] * t[i] + c[i])
+    }
 }
@@ -1,5 +1,5 @@
-use ffxiv_parser::Pap;
-use havok_parser::{HavokAnimationContainer, HavokBinaryTagFileReader};
+use ffxiv_parser::{Pap, Sklb};
+use havok_parser::{HavokAnimationContainer, HavokReader};
 use sqpack::Result;
 use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
 
@@ -17,9 +17,9 @@ async fn pap_test() -> Result<()> {
     let pap = Pap::new(&pack, "chara/human/c1101/animation/a0001/bt_common/resident/idle.pap").await?;
     let hkx = pap.hkx_data();
 
-    let root = HavokBinaryTagFileReader::read(hkx);
+    let root = HavokReader::read(hkx).unwrap();
     let raw_animation_container = root.find_object_by_type("hkaAnimationContainer");
-    let animation_container = HavokAnimationContainer::new(raw_animation_container);
+    let animation_container = HavokAnimationContainer::new(raw_animation_container).unwrap();
 
     let havok_animation_binding = &animation_container.bindings[1];
     assert_eq!(havok_animation_binding.transform_track_to_bone_indices[0], 1);
@@ -34,3 +34,108 @@ async fn pap_test() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn pap_extras_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .filter(Some("havok_parser"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let sklb = Sklb::new(&pack, "chara/human/c1101/skeleton/base/b0001/skl_c1101b0001.sklb").await?;
+    let skeleton_root = HavokReader::read(sklb.hkx_data()).unwrap();
+    let skeleton_container = HavokAnimationContainer::new(skeleton_root.find_object_by_type("hkaAnimationContainer")).unwrap();
+    let bone_names = &skeleton_container.skeletons[0].bone_names;
+
+    let pap = Pap::new(&pack, "chara/human/c1101/animation/a0001/bt_common/resident/idle.pap").await?;
+    let root = HavokReader::read(pap.hkx_data()).unwrap();
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    let binding = &animation_container.bindings[1];
+    let animation = &binding.animation;
+    let duration = animation.duration() * 1000.;
+
+    // body animation doesn't drive float slots
+    assert!(binding.float_track_to_float_slot_indices.is_empty());
+    assert_eq!(animation.sample_floats(duration / 2.).len(), 0);
+
+    // annotation tracks are named after bones of matching transform tracks, annotation time is in seconds
+    let annotations = animation.annotations();
+    assert_eq!(annotations.len(), binding.transform_track_to_bone_indices.len());
+    assert_eq!(annotations[0].name, "n_hara");
+    for (track, &bone_index) in annotations.iter().zip(binding.transform_track_to_bone_indices.iter()) {
+        assert_eq!(track.name, bone_names[bone_index as usize]);
+        assert!(track.annotations.iter().all(|x| x.time >= 0. && x.time <= animation.duration()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pap_float_track_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .filter(Some("havok_parser"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pap = Pap::new(&pack, "chara/human/c1101/animation/f0001/resident/face.pap").await?;
+    let root = HavokReader::read(pap.hkx_data()).unwrap();
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    let binding = animation_container
+        .bindings
+        .iter()
+        .find(|x| !x.float_track_to_float_slot_indices.is_empty())
+        .unwrap();
+    let animation = &binding.animation;
+    let duration = animation.duration() * 1000.;
+
+    // one value per float track, at any time
+    let track_count = binding.float_track_to_float_slot_indices.len();
+    let samples = [0., duration / 2., duration].map(|x| animation.sample_floats(x));
+    assert!(samples.iter().all(|x| x.len() == track_count && x.iter().all(|x| x.is_finite())));
+    assert!(samples.iter().any(|x| x.iter().any(|&x| x != 0.)));
+
+    Ok(())
+}
+
+#[allow(clippy::float_cmp)]
+#[tokio::test]
+async fn pap_root_motion_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .filter(Some("havok_parser"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pap = Pap::new(&pack, "chara/human/c1101/animation/a0001/bt_common/emote/dance02.pap").await?;
+    let root = HavokReader::read(pap.hkx_data()).unwrap();
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    let binding = animation_container
+        .bindings
+        .iter()
+        .find(|x| x.animation.extracted_motion().is_some())
+        .unwrap();
+    let animation = &binding.animation;
+    let extracted_motion = animation.extracted_motion().unwrap();
+    let (first, last) = (extracted_motion.samples[0], *extracted_motion.samples.last().unwrap());
+
+    // sampling takes milliseconds, and ends at the last reference frame sample
+    let start = animation.sample_root_motion(0.).unwrap();
+    assert_eq!(start.translation, [first[0], first[1], first[2], 0.]);
+
+    let end = animation.sample_root_motion(animation.duration() * 1000.).unwrap();
+    assert_eq!(end.translation, [last[0], last[1], last[2], 0.]);
+    assert_ne!(end.translation, start.translation);
+
+    Ok(())
+}
@@ -0,0 +1,611 @@
+use std::{cell::RefCell, rc::Rc};
+
+use hashbrown::HashMap;
+
+use ffxiv_parser::{Pap, Sklb};
+use havok_parser::{
+    HavokAnimation, HavokAnimationBinding, HavokAnimationBlendHint, HavokAnimationContainer, HavokAnimationContainerBuilder,
+    HavokBinaryTagFileReader, HavokBinaryTagFileWriter, HavokError, HavokObject, HavokObjectType, HavokObjectTypeMember, HavokReader, HavokTransform,
+    HavokValue, HavokValueType,
+};
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+fn object(type_name: &str, members: Vec<(&str, HavokValue)>) -> Rc<RefCell<HavokObject>> {
+    let type_members = members
+        .iter()
+        .map(|(name, _)| HavokObjectTypeMember::new(Rc::from(*name), HavokValueType::EMPTY, 0, None))
+        .collect::<Vec<_>>();
+    let object_type = Rc::new(HavokObjectType::new(Rc::from(type_name), None, type_members));
+
+    let data = members
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, value))| (index, value))
+        .collect::<HashMap<_, _>>();
+
+    Rc::new(RefCell::new(HavokObject::new(object_type, data)))
+}
+
+fn binding(animation: Rc<RefCell<HavokObject>>) -> HavokAnimationBinding {
+    HavokAnimationBinding::new(object(
+        "hkaAnimationBinding",
+        vec![
+            ("transformTrackToBoneIndices", HavokValue::Array(vec![HavokValue::Integer(0)])),
+            ("blendHint", HavokValue::Integer(0)),
+            ("animation", HavokValue::Object(animation)),
+        ],
+    ))
+    .unwrap()
+}
+
+fn bytes(data: &[u8]) -> HavokValue {
+    HavokValue::Array(data.iter().map(|&x| HavokValue::Integer(x as i32)).collect())
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn interleaved_uncompressed_test() {
+    let transform = |x: f32, y: f32, z: f32| HavokValue::Vec(vec![x, y, z, 0., 0., 0., 0., 1., 1., 1., 1., 1.]);
+
+    let animation = object(
+        "hkaInterleavedUncompressedAnimation",
+        vec![
+            ("duration", HavokValue::Real(1.)),
+            ("numberOfTransformTracks", HavokValue::Integer(1)),
+            ("transforms", HavokValue::Array(vec![transform(0., 0., 0.), transform(2., 4., 6.)])),
+            ("floats", HavokValue::Array(Vec::new())),
+        ],
+    );
+    let binding = binding(animation);
+
+    assert_eq!(binding.animation.duration(), 1.);
+
+    let frame = binding.animation.sample(500.);
+    assert_eq!(frame[0].translation, [1., 2., 3., 0.]);
+    assert_eq!(frame[0].rotation, [0., 0., 0., 1.]);
+    assert_eq!(frame[0].scale, [1., 1., 1., 1.]);
+
+    let frame = binding.animation.sample(1000.);
+    assert_eq!(frame[0].translation, [2., 4., 6., 0.]);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn quantized_test() {
+    let mut data = Vec::new();
+    // header: 1 bone, frame size 6, 1 static scale, 3 dynamic translations
+    for value in [44u16, 1, 0, 6, 0, 0, 1, 0, 3, 0, 0, 0, 44, 48, 52, 60, 72, 84] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&0.5f32.to_le_bytes()); // frame duration
+    data.extend_from_slice(&2u32.to_le_bytes()); // frame count
+
+    data.extend_from_slice(&8u16.to_le_bytes()); // static elements: scale x
+    data.extend_from_slice(&[0, 0]);
+    data.extend_from_slice(&2f32.to_le_bytes()); // static values
+    for element in [0u16, 1, 2] {
+        data.extend_from_slice(&element.to_le_bytes()); // dynamic elements: translation xyz
+    }
+    data.extend_from_slice(&[0, 0]);
+    for _ in 0..3 {
+        data.extend_from_slice(&0f32.to_le_bytes()); // range minimums
+    }
+    for _ in 0..3 {
+        data.extend_from_slice(&2f32.to_le_bytes()); // range spans
+    }
+    data.extend_from_slice(&[0; 6]); // frame 0
+    data.extend_from_slice(&[0xff; 6]); // frame 1
+
+    let animation = object(
+        "hkaQuantizedAnimation",
+        vec![
+            ("duration", HavokValue::Real(0.5)),
+            ("numberOfTransformTracks", HavokValue::Integer(1)),
+            ("data", bytes(&data)),
+        ],
+    );
+    let binding = binding(animation);
+
+    let frame = binding.animation.sample(0.);
+    assert_eq!(frame[0].translation, [0., 0., 0., 0.]);
+    assert_eq!(frame[0].scale, [2., 1., 1., 1.]);
+
+    let frame = binding.animation.sample(250.);
+    assert_eq!(frame[0].translation, [1., 1., 1., 0.]);
+    assert_eq!(frame[0].rotation, [0., 0., 0., 1.]);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn quantized_out_of_range_element_test() {
+    let mut data = Vec::new();
+    // header: 1 bone, 3 static translations: rotation x, bone 2 translation x, translation y
+    for value in [44u16, 1, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 44, 52, 64, 64, 64, 64] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&0f32.to_le_bytes()); // frame duration
+    data.extend_from_slice(&1u32.to_le_bytes()); // frame count
+
+    for element in [4u16, 24, 1] {
+        data.extend_from_slice(&element.to_le_bytes()); // static elements
+    }
+    data.extend_from_slice(&[0, 0]);
+    for value in [5f32, 6., 7.] {
+        data.extend_from_slice(&value.to_le_bytes()); // static values
+    }
+
+    let animation = object(
+        "hkaQuantizedAnimation",
+        vec![
+            ("duration", HavokValue::Real(0.)),
+            ("numberOfTransformTracks", HavokValue::Integer(1)),
+            ("data", bytes(&data)),
+        ],
+    );
+    let binding = binding(animation);
+
+    let frame = binding.animation.sample(0.);
+    assert_eq!(frame.len(), 1);
+    assert_eq!(frame[0].translation, [0., 7., 0., 0.]);
+    assert_eq!(frame[0].rotation, [0., 0., 0., 1.]);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn delta_compressed_test() {
+    let mut data = vec![0, 4]; // quantized data: translation x, absolute 0 then delta 4
+    data.extend_from_slice(&0x03feu16.to_le_bytes()); // static mask: everything but translation x
+    for value in [0f32, 0., 0., 0., 0., 1., 1., 1., 1.] {
+        data.extend_from_slice(&value.to_le_bytes()); // static dofs
+    }
+    data.extend_from_slice(&0f32.to_le_bytes()); // offset
+    data.extend_from_slice(&0.25f32.to_le_bytes()); // scale
+    data.push(8); // bit width
+
+    let quantization_format = object(
+        "hkaDeltaCompressedAnimationQuantizationFormat",
+        vec![
+            ("maxBitWidth", HavokValue::Integer(8)),
+            ("preserved", HavokValue::Integer(0)),
+            ("numD", HavokValue::Integer(1)),
+            ("offsetIdx", HavokValue::Integer(40)),
+            ("scaleIdx", HavokValue::Integer(44)),
+            ("bitWidthIdx", HavokValue::Integer(48)),
+        ],
+    );
+
+    let animation = object(
+        "hkaDeltaCompressedAnimation",
+        vec![
+            ("duration", HavokValue::Real(1.)),
+            ("numberOfTransformTracks", HavokValue::Integer(1)),
+            ("numberOfPoses", HavokValue::Integer(2)),
+            ("blockSize", HavokValue::Integer(2)),
+            ("qFormat", HavokValue::Object(quantization_format)),
+            ("quantizedDataIdx", HavokValue::Integer(0)),
+            ("staticMaskIdx", HavokValue::Integer(2)),
+            ("staticDOFsIdx", HavokValue::Integer(4)),
+            ("totalBlockSize", HavokValue::Integer(2)),
+            ("lastBlockSize", HavokValue::Integer(2)),
+            ("dataBuffer", bytes(&data)),
+        ],
+    );
+    let binding = binding(animation);
+
+    let frame = binding.animation.sample(1000.);
+    assert_eq!(frame[0].translation, [1., 0., 0., 0.]);
+    assert_eq!(frame[0].rotation, [0., 0., 0., 1.]);
+    assert_eq!(frame[0].scale, [1., 1., 1., 1.]);
+
+    let frame = binding.animation.sample(500.);
+    assert_eq!(frame[0].translation, [0.5, 0., 0., 0.]);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn annotation_float_extracted_motion_test() {
+    let annotation = object(
+        "hkaAnnotationTrackAnnotation",
+        vec![("time", HavokValue::Real(0.5)), ("text", HavokValue::String(Rc::from("SE_footstep")))],
+    );
+    let annotation_track = object(
+        "hkaAnnotationTrack",
+        vec![
+            ("trackName", HavokValue::String(Rc::from("n_root"))),
+            ("annotations", HavokValue::Array(vec![HavokValue::Object(annotation)])),
+        ],
+    );
+    let extracted_motion = object(
+        "hkaDefaultAnimatedReferenceFrame",
+        vec![
+            ("up", HavokValue::Vec(vec![0., 1., 0., 0.])),
+            ("forward", HavokValue::Vec(vec![0., 0., 1., 0.])),
+            ("duration", HavokValue::Real(1.)),
+            (
+                "referenceFrameSamples",
+                HavokValue::Array(vec![HavokValue::Vec(vec![0., 0., 0., 0.]), HavokValue::Vec(vec![0., 0., 2., 0.])]),
+            ),
+        ],
+    );
+
+    let transform = || HavokValue::Vec(vec![0., 0., 0., 0., 0., 0., 0., 1., 1., 1., 1., 1.]);
+    let animation = object(
+        "hkaInterleavedUncompressedAnimation",
+        vec![
+            ("duration", HavokValue::Real(1.)),
+            ("numberOfTransformTracks", HavokValue::Integer(1)),
+            ("numberOfFloatTracks", HavokValue::Integer(2)),
+            ("extractedMotion", HavokValue::Object(extracted_motion)),
+            ("annotationTracks", HavokValue::Array(vec![HavokValue::Object(annotation_track)])),
+            ("transforms", HavokValue::Array(vec![transform(), transform()])),
+            ("floats", HavokValue::Array([0., 1., 1., 0.].into_iter().map(HavokValue::Real).collect())),
+        ],
+    );
+    let binding = binding(animation);
+
+    let annotations = binding.animation.annotations();
+    assert_eq!(annotations[0].name, "n_root");
+    assert_eq!(annotations[0].annotations[0].time, 0.5);
+    assert_eq!(annotations[0].annotations[0].text, "SE_footstep");
+
+    assert_eq!(binding.animation.sample_floats(500.), [0.5, 0.5]);
+
+    let root_motion = binding.animation.sample_root_motion(500.).unwrap();
+    assert_eq!(root_motion.translation, [0., 0., 1., 0.]);
+    assert_eq!(root_motion.rotation, [0., 0., 0., 1.]);
+}
+
+// idle pap resampled at 30 fps, used as known values to check the other animation types against
+struct RealAssetFrames {
+    skeleton: HavokAnimationContainer,
+    track_to_bone_indices: Vec<u16>,
+    duration: f32,
+    frames: Vec<Vec<HavokTransform>>,
+}
+
+impl RealAssetFrames {
+    async fn read() -> sqpack::Result<Self> {
+        let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+        let pack = SqPackReaderExtractedFile::new(provider);
+
+        let sklb = Sklb::new(&pack, "chara/human/c1101/skeleton/base/b0001/skl_c1101b0001.sklb").await?;
+        let skeleton_root = HavokReader::read(sklb.hkx_data()).unwrap();
+        let skeleton = HavokAnimationContainer::new(skeleton_root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+        let pap = Pap::new(&pack, "chara/human/c1101/animation/a0001/bt_common/resident/idle.pap").await?;
+        let root = HavokReader::read(pap.hkx_data()).unwrap();
+        let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+        let spline = &animation_container.bindings[1];
+
+        let duration = spline.animation.duration();
+        let frame_count = (duration * 30.) as usize + 1;
+        let frames = (0..frame_count)
+            .map(|x| spline.animation.sample(Self::frame_time(duration, frame_count, x)))
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            skeleton,
+            track_to_bone_indices: spline.transform_track_to_bone_indices.clone(),
+            duration,
+            frames,
+        })
+    }
+
+    fn frame_time(duration: f32, frame_count: usize, frame: usize) -> f32 {
+        frame as f32 * duration * 1000. / (frame_count - 1) as f32
+    }
+
+    fn track_count(&self) -> usize {
+        self.frames[0].len()
+    }
+
+    fn track_values(&self, track: usize, extract: impl Fn(&HavokTransform) -> f32) -> Vec<f32> {
+        self.frames.iter().map(|x| extract(&x[track])).collect()
+    }
+
+    fn binding(&self, animation: Rc<RefCell<HavokObject>>) -> HavokAnimationBinding {
+        HavokAnimationBinding::new(object(
+            "hkaAnimationBinding",
+            vec![
+                (
+                    "transformTrackToBoneIndices",
+                    HavokValue::Array(self.track_to_bone_indices.iter().map(|&x| HavokValue::Integer(x as i32)).collect()),
+                ),
+                ("blendHint", HavokValue::Integer(0)),
+                ("animation", HavokValue::Object(animation)),
+            ],
+        ))
+        .unwrap()
+    }
+
+    fn assert_matches(&self, animation: &dyn HavokAnimation, tolerance: f32) {
+        let frame_count = self.frames.len();
+        assert_eq!(animation.duration(), self.duration);
+
+        for frame in [0, 1, frame_count / 2, frame_count - 1] {
+            let expected = &self.frames[frame];
+            let actual = animation.sample(Self::frame_time(self.duration, frame_count, frame));
+
+            assert_eq!(expected.len(), actual.len());
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                // q and -q are the same rotation
+                let sign = if expected.rotation.iter().zip(actual.rotation.iter()).map(|(a, b)| a * b).sum::<f32>() < 0. {
+                    -1.
+                } else {
+                    1.
+                };
+                for i in 0..3 {
+                    assert!((expected.translation[i] - actual.translation[i]).abs() < tolerance);
+                    assert!((expected.scale[i] - actual.scale[i]).abs() < tolerance);
+                }
+                for i in 0..4 {
+                    assert!((expected.rotation[i] - sign * actual.rotation[i]).abs() < tolerance);
+                }
+            }
+        }
+    }
+}
+
+fn pack_quaternion_48(rotation: [f32; 4]) -> [u8; 6] {
+    let missing_index = (0..4).max_by(|&a, &b| rotation[a].abs().total_cmp(&rotation[b].abs())).unwrap();
+    let items = (0..4)
+        .filter(|&x| x != missing_index)
+        .map(|x| ((rotation[x] * 23169. + 16383.).round() as u16).min(0x7fff))
+        .collect::<Vec<_>>();
+
+    let data = [
+        items[0] | (((missing_index & 1) as u16) << 15),
+        items[1] | (((missing_index >> 1) as u16) << 15),
+        items[2] | (u16::from(rotation[missing_index] < 0.) << 15),
+    ];
+
+    let mut result = [0; 6];
+    for (i, item) in data.iter().enumerate() {
+        result[i * 2..i * 2 + 2].copy_from_slice(&item.to_le_bytes());
+    }
+    result
+}
+
+#[tokio::test]
+async fn interleaved_real_asset_test() -> sqpack::Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let real = RealAssetFrames::read().await?;
+
+    // decode resampled spline compressed animation back as interleaved
+    let mut builder = HavokAnimationContainerBuilder::new();
+    builder
+        .add_skeleton("skeleton", &real.skeleton.skeletons[0])
+        .add_uncompressed_animation(
+            "skeleton",
+            &real.track_to_bone_indices,
+            HavokAnimationBlendHint::Normal,
+            real.duration,
+            &real.frames,
+        )
+        .unwrap();
+    let data = HavokBinaryTagFileWriter::write(&builder.build());
+
+    let written_root = HavokBinaryTagFileReader::read(&data);
+    let written = HavokAnimationContainer::new(written_root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    real.assert_matches(written.bindings[0].animation.as_ref(), 1e-4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn quantized_real_asset_test() -> sqpack::Result<()> {
+    let real = RealAssetFrames::read().await?;
+    let track_count = real.track_count();
+
+    // every track is dynamic: translation xyz, rotation, scale xyz
+    let scalar_elements = (0..track_count)
+        .flat_map(|x| [0, 1, 2].map(|c| x * 12 + c))
+        .chain((0..track_count).flat_map(|x| [8, 9, 10].map(|c| x * 12 + c)))
+        .collect::<Vec<_>>();
+    let rotation_elements = (0..track_count).map(|x| x * 12 + 4).collect::<Vec<_>>();
+    let scalar_values = scalar_elements
+        .iter()
+        .map(|&element| {
+            let component = element % 12;
+            real.track_values(element / 12, |x| {
+                if component < 4 {
+                    x.translation[component]
+                } else {
+                    x.scale[component - 8]
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let minimums = scalar_values
+        .iter()
+        .map(|x| x.iter().copied().fold(f32::MAX, f32::min))
+        .collect::<Vec<_>>();
+    let spans = scalar_values
+        .iter()
+        .zip(minimums.iter())
+        .map(|(x, minimum)| x.iter().copied().fold(f32::MIN, f32::max) - minimum)
+        .collect::<Vec<_>>();
+
+    let header_size = 44;
+    let elements_size = (scalar_elements.len() + rotation_elements.len()) * 2;
+    let minimums_offset = (header_size + elements_size).next_multiple_of(4);
+    let spans_offset = minimums_offset + scalar_elements.len() * 4;
+    let values_offset = spans_offset + scalar_elements.len() * 4;
+    let frame_size = scalar_elements.len() * 2 + rotation_elements.len() * 6;
+
+    let mut data = Vec::new();
+    let (translation_count, scale_count) = (track_count * 3, track_count * 3);
+    #[rustfmt::skip]
+    let header = [
+        header_size, track_count, 0, frame_size, 0, 0, 0, 0, translation_count, track_count, scale_count, 0,
+        header_size, header_size, header_size, minimums_offset, spans_offset, values_offset,
+    ];
+    for value in header {
+        data.extend_from_slice(&(value as u16).to_le_bytes());
+    }
+    data.extend_from_slice(&(real.duration / (real.frames.len() - 1) as f32).to_le_bytes());
+    data.extend_from_slice(&(real.frames.len() as u32).to_le_bytes());
+
+    // dynamic elements are translations, rotations, then scales
+    let (translation_elements, scale_elements) = scalar_elements.split_at(translation_count);
+    for &element in translation_elements.iter().chain(rotation_elements.iter()).chain(scale_elements.iter()) {
+        data.extend_from_slice(&(element as u16).to_le_bytes());
+    }
+    data.resize(minimums_offset, 0);
+    for value in minimums.iter().chain(spans.iter()) {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let quantize = |index: usize, frame: usize| {
+        if spans[index] == 0. {
+            0
+        } else {
+            ((scalar_values[index][frame] - minimums[index]) / spans[index] * u16::MAX as f32).round() as u16
+        }
+    };
+    for (frame, pose) in real.frames.iter().enumerate() {
+        for index in 0..translation_count {
+            data.extend_from_slice(&quantize(index, frame).to_le_bytes());
+        }
+        for transform in pose {
+            data.extend_from_slice(&pack_quaternion_48(transform.rotation));
+        }
+        for index in translation_count..translation_count + scale_count {
+            data.extend_from_slice(&quantize(index, frame).to_le_bytes());
+        }
+    }
+
+    let animation = object(
+        "hkaQuantizedAnimation",
+        vec![
+            ("duration", HavokValue::Real(real.duration)),
+            ("numberOfTransformTracks", HavokValue::Integer(track_count as i32)),
+            ("data", bytes(&data)),
+        ],
+    );
+    let binding = real.binding(animation);
+
+    real.assert_matches(binding.animation.as_ref(), 1e-3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delta_compressed_real_asset_test() -> sqpack::Result<()> {
+    const BLOCK_SIZE: usize = 8;
+    const BIT_WIDTH: usize = 16;
+
+    let real = RealAssetFrames::read().await?;
+    let track_count = real.track_count();
+    let pose_count = real.frames.len();
+
+    // every dof is dynamic, quantized to 15 bits so deltas fit in signed 16 bits
+    let dof_values = (0..track_count)
+        .flat_map(|track| {
+            (0..10).map(move |dof| match dof {
+                0..3 => (track, 0, dof),
+                3..7 => (track, 1, dof - 3),
+                _ => (track, 2, dof - 7),
+            })
+        })
+        .map(|(track, kind, index)| {
+            real.track_values(track, |x| match kind {
+                0 => x.translation[index],
+                1 => x.rotation[index],
+                _ => x.scale[index],
+            })
+        })
+        .collect::<Vec<_>>();
+    let offsets = dof_values.iter().map(|x| x.iter().copied().fold(f32::MAX, f32::min)).collect::<Vec<_>>();
+    let scales = dof_values
+        .iter()
+        .zip(offsets.iter())
+        .map(|(x, offset)| (x.iter().copied().fold(f32::MIN, f32::max) - offset).max(f32::EPSILON) / 32767.)
+        .collect::<Vec<_>>();
+    let quantized = dof_values
+        .iter()
+        .zip(offsets.iter().zip(scales.iter()))
+        .map(|(x, (offset, scale))| x.iter().map(|value| ((value - offset) / scale).round() as i32).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let block_count = pose_count.div_ceil(BLOCK_SIZE);
+    let last_block_size = pose_count - (block_count - 1) * BLOCK_SIZE;
+    let total_block_size = dof_values.len() * BLOCK_SIZE * BIT_WIDTH / 8;
+
+    let mut data = Vec::new();
+    for block in 0..block_count {
+        let poses = block * BLOCK_SIZE..usize::min((block + 1) * BLOCK_SIZE, pose_count);
+        let block_start = data.len();
+        for values in &quantized {
+            let block_values = &values[poses.clone()];
+            data.extend_from_slice(&(block_values[0] as u16).to_le_bytes());
+            for pair in block_values.windows(2) {
+                data.extend_from_slice(&((pair[1] - pair[0]) as i16).to_le_bytes());
+            }
+        }
+        data.resize(block_start + total_block_size, 0);
+    }
+    let static_mask_index = data.len();
+    data.resize(data.len() + track_count * 2, 0);
+    let offset_index = data.len();
+    for value in offsets.iter().chain(scales.iter()) {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    let scale_index = offset_index + offsets.len() * 4;
+    let bit_width_index = data.len();
+    data.resize(data.len() + dof_values.len(), BIT_WIDTH as u8);
+
+    let quantization_format = object(
+        "hkaDeltaCompressedAnimationQuantizationFormat",
+        vec![
+            ("maxBitWidth", HavokValue::Integer(BIT_WIDTH as i32)),
+            ("preserved", HavokValue::Integer(0)),
+            ("numD", HavokValue::Integer(dof_values.len() as i32)),
+            ("offsetIdx", HavokValue::Integer(offset_index as i32)),
+            ("scaleIdx", HavokValue::Integer(scale_index as i32)),
+            ("bitWidthIdx", HavokValue::Integer(bit_width_index as i32)),
+        ],
+    );
+
+    let animation = object(
+        "hkaDeltaCompressedAnimation",
+        vec![
+            ("duration", HavokValue::Real(real.duration)),
+            ("numberOfTransformTracks", HavokValue::Integer(track_count as i32)),
+            ("numberOfPoses", HavokValue::Integer(pose_count as i32)),
+            ("blockSize", HavokValue::Integer(BLOCK_SIZE as i32)),
+            ("qFormat", HavokValue::Object(quantization_format)),
+            ("quantizedDataIdx", HavokValue::Integer(0)),
+            ("staticMaskIdx", HavokValue::Integer(static_mask_index as i32)),
+            ("staticDOFsIdx", HavokValue::Integer(offset_index as i32)),
+            ("totalBlockSize", HavokValue::Integer(total_block_size as i32)),
+            ("lastBlockSize", HavokValue::Integer(last_block_size as i32)),
+            ("dataBuffer", bytes(&data)),
+        ],
+    );
+    let binding = real.binding(animation);
+
+    real.assert_matches(binding.animation.as_ref(), 1e-3);
+
+    Ok(())
+}
+
+#[test]
+fn unsupported_animation_type_test() {
+    let animation = object("hkaPredictiveCompressedAnimation", vec![("duration", HavokValue::Real(1.))]);
+
+    let result = HavokAnimationBinding::new(object(
+        "hkaAnimationBinding",
+        vec![
+            ("transformTrackToBoneIndices", HavokValue::Array(vec![HavokValue::Integer(0)])),
+            ("blendHint", HavokValue::Integer(0)),
+            ("animation", HavokValue::Object(animation)),
+        ],
+    ));
+
+    assert!(matches!(result, Err(HavokError::UnsupportedAnimationType(x)) if x == "hkaPredictiveCompressedAnimation"));
+}
@@ -0,0 +1,202 @@
+use ffxiv_parser::Sklb;
+use havok_parser::{HavokAnimationContainer, HavokError, HavokPackfileReader, HavokReader};
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+fn write(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+fn section_header(tag: &str, data_start: usize, fixups: [usize; 6]) -> Vec<u8> {
+    let mut result = vec![0; 20];
+    write(&mut result, 0, tag.as_bytes());
+
+    result.extend_from_slice(&(data_start as u32).to_le_bytes());
+    for fixup in fixups {
+        result.extend_from_slice(&(fixup as u32).to_le_bytes());
+    }
+
+    result
+}
+
+// 64bit little endian packfile containing a skeleton with single bone
+fn build_packfile() -> Vec<u8> {
+    let mut class_names = Vec::new();
+    let mut class_name_offsets = Vec::new();
+    for name in ["hkRootLevelContainer", "hkaAnimationContainer", "hkaSkeleton"] {
+        class_names.extend_from_slice(&[0, 0, 0, 0, 0x09]);
+        class_name_offsets.push(class_names.len());
+        class_names.extend_from_slice(name.as_bytes());
+        class_names.push(0);
+    }
+    while class_names.len() % 16 != 0 {
+        class_names.push(0xff);
+    }
+
+    let mut data = vec![0; 0x1c0];
+    write(&mut data, 0x08, &1u32.to_le_bytes()); // namedVariants size
+    write(&mut data, 0x30, b"Merged Animation Container\0");
+    write(&mut data, 0x50, b"hkaAnimationContainer\0");
+    write(&mut data, 0x88, &1u32.to_le_bytes()); // skeletons size
+    write(&mut data, 0x100, &1u32.to_le_bytes()); // parentIndices size
+    write(&mut data, 0x110, &1u32.to_le_bytes()); // bones size
+    write(&mut data, 0x120, &1u32.to_le_bytes()); // referencePose size
+    write(&mut data, 0x150, b"skel\0");
+    write(&mut data, 0x160, &(-1i16).to_le_bytes());
+    write(&mut data, 0x180, b"root\0");
+    for (index, value) in [1f32, 2., 3., 0., 0., 0., 0., 1., 1., 1., 1., 1.].iter().enumerate() {
+        write(&mut data, 0x190 + index * 4, &value.to_le_bytes());
+    }
+
+    let local_fixups = [
+        (0x00, 0x10),
+        (0x10, 0x30),
+        (0x18, 0x50),
+        (0x80, 0xd0),
+        (0xf0, 0x150),
+        (0xf8, 0x160),
+        (0x108, 0x170),
+        (0x170, 0x180),
+        (0x118, 0x190),
+    ];
+    let global_fixups = [(0x20, 1, 0x70), (0xd0, 1, 0xe0)];
+    let virtual_fixups = [(0x70, 0, class_name_offsets[1]), (0xe0, 0, class_name_offsets[2])];
+
+    let local_offset = data.len();
+    for (source, destination) in local_fixups {
+        data.extend_from_slice(&(source as u32).to_le_bytes());
+        data.extend_from_slice(&(destination as u32).to_le_bytes());
+    }
+    let global_offset = data.len();
+    for (source, section, destination) in global_fixups.into_iter().chain(virtual_fixups) {
+        data.extend_from_slice(&(source as u32).to_le_bytes());
+        data.extend_from_slice(&(section as u32).to_le_bytes());
+        data.extend_from_slice(&(destination as u32).to_le_bytes());
+    }
+    let virtual_offset = global_offset + global_fixups.len() * 12;
+    let end = data.len();
+
+    let mut result = Vec::new();
+    for value in [0x57e0_e057u32, 0x10c0_c010, 0, 8] {
+        result.extend_from_slice(&value.to_le_bytes());
+    }
+    result.extend_from_slice(&[8, 1, 0, 1]); // layout rules
+    for value in [2u32, 1, 0, 0, class_name_offsets[0] as u32] {
+        result.extend_from_slice(&value.to_le_bytes());
+    }
+    result.extend_from_slice(b"hk_2010.2.0-r1\0\0");
+    result.extend_from_slice(&0u32.to_le_bytes()); // flags
+    result.extend_from_slice(&[0xff, 0xff, 0, 0]); // predicates
+
+    let class_names_start = result.len() + 2 * 48;
+    let data_start = class_names_start + class_names.len();
+    let class_names_len = class_names.len();
+    result.extend(section_header("__classnames__", class_names_start, [class_names_len; 6]));
+    result.extend(section_header(
+        "__data__",
+        data_start,
+        [local_offset, global_offset, virtual_offset, end, end, end],
+    ));
+    result.extend(class_names);
+    result.extend(data);
+
+    result
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn packfile_test() {
+    let data = build_packfile();
+
+    let root = HavokPackfileReader::read(&data).unwrap();
+    let raw_animation_container = root.find_object_by_type("hkaAnimationContainer");
+    assert_eq!(&*raw_animation_container.borrow().object_type.name, "hkaAnimationContainer");
+
+    let animation_container = HavokAnimationContainer::new(raw_animation_container).unwrap();
+    assert_eq!(animation_container.skeletons.len(), 1);
+    assert!(animation_container.bindings.is_empty());
+
+    let skeleton = &animation_container.skeletons[0];
+    assert_eq!(skeleton.bone_names, ["root"]);
+    assert_eq!(skeleton.reference_pose[0].translation, [1., 2., 3., 0.]);
+    assert_eq!(skeleton.reference_pose[0].rotation, [0., 0., 0., 1.]);
+}
+
+#[test]
+fn reader_dispatch_test() {
+    let data = build_packfile();
+
+    let root = HavokReader::read(&data).unwrap();
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+    assert_eq!(animation_container.skeletons[0].bone_names, ["root"]);
+
+    assert!(matches!(HavokReader::read(&[0; 16]), Err(HavokError::UnknownSignature)));
+    assert!(matches!(HavokReader::read(&[]), Err(HavokError::UnknownSignature)));
+}
+
+#[test]
+fn unsupported_layout_test() {
+    let mut data = build_packfile();
+    data[0x11] = 0; // big endian
+
+    assert!(matches!(
+        HavokPackfileReader::read(&data),
+        Err(HavokError::UnsupportedLayout {
+            pointer_size: 8,
+            little_endian: false
+        })
+    ));
+
+    let mut data = build_packfile();
+    data[0x10] = 2; // pointer size
+
+    assert!(matches!(
+        HavokReader::read(&data),
+        Err(HavokError::UnsupportedLayout {
+            pointer_size: 2,
+            little_endian: true
+        })
+    ));
+
+    assert!(matches!(HavokPackfileReader::read(&data[..32]), Err(HavokError::UnknownSignature)));
+}
+
+#[tokio::test]
+async fn real_packfile_test() -> sqpack::Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    // skeletons which may ship as packfile; at least one of them has to
+    let candidates = [
+        "chara/monster/m0001/skeleton/base/b0001/skl_m0001b0001.sklb",
+        "chara/demihuman/d1001/skeleton/base/b0001/skl_d1001b0001.sklb",
+        "chara/weapon/w0101/skeleton/base/b0001/skl_w0101b0001.sklb",
+        "chara/human/c0101/skeleton/base/b0001/skl_c0101b0001.sklb",
+    ];
+
+    let mut packfile_count = 0;
+    for path in candidates {
+        let sklb = Sklb::new(&pack, path).await?;
+        let hkx = sklb.hkx_data();
+        if hkx[0..8] != [0x57, 0xe0, 0xe0, 0x57, 0x10, 0xc0, 0xc0, 0x10] {
+            continue;
+        }
+        packfile_count += 1;
+
+        let root = HavokPackfileReader::read(hkx).unwrap();
+        let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+        let skeleton = &animation_container.skeletons[0];
+
+        assert!(!skeleton.bone_names.is_empty());
+        assert_eq!(skeleton.parent_indices.len(), skeleton.bone_names.len());
+        assert_eq!(skeleton.reference_pose.len(), skeleton.bone_names.len());
+        assert_eq!(skeleton.parent_indices[0], usize::MAX);
+        assert_eq!(skeleton.bone_names[0], "n_root");
+    }
+    assert!(packfile_count > 0);
+
+    Ok(())
+}
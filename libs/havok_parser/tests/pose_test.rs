@@ -0,0 +1,94 @@
+use havok_parser::{
+    HavokAnimationBlendHint, HavokAnimationContainer, HavokAnimationContainerBuilder, HavokBinaryTagFileReader, HavokBinaryTagFileWriter,
+    HavokSkeleton, HavokTransform,
+};
+
+fn assert_near(a: &[f32], b: &[f32]) {
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert!((a - b).abs() < 1e-5, "{:?} != {:?}", a, b);
+    }
+}
+
+// 90 degrees around y
+const ROTATION_Y_90: [f32; 4] = [0., std::f32::consts::FRAC_1_SQRT_2, 0., std::f32::consts::FRAC_1_SQRT_2];
+
+fn translation(x: f32, y: f32, z: f32) -> HavokTransform {
+    HavokTransform::from_trs([x, y, z, 0.], [0., 0., 0., 1.], [1., 1., 1., 1.])
+}
+
+#[test]
+fn transform_test() {
+    let parent = HavokTransform::from_trs([0., 1., 0., 0.], ROTATION_Y_90, [2., 2., 2., 1.]);
+    let model = parent.multiply(&translation(1., 0., 0.));
+    assert_near(&model.translation, &[0., 1., -2., 0.]);
+    assert_near(&model.rotation, &ROTATION_Y_90);
+    assert_near(&model.scale, &[2., 2., 2., 1.]);
+
+    let matrix = parent.to_matrix();
+    assert_near(&matrix[0..4], &[0., 0., -2., 0.]);
+    assert_near(&matrix[12..16], &[0., 1., 0., 1.]);
+
+    let half = HavokTransform::identity().slerp(&HavokTransform::from_trs([0., 0., 0., 0.], ROTATION_Y_90, [1., 1., 1., 1.]), 0.5);
+    let angle = std::f32::consts::FRAC_PI_8;
+    assert_near(&half.rotation, &[0., angle.sin(), 0., angle.cos()]);
+
+    let blended = HavokTransform::blend_poses(&[translation(0., 0., 0.)], &[translation(4., 0., 0.)], 0.25);
+    assert_near(&blended[0].translation, &[1., 0., 0., 0.]);
+}
+
+#[test]
+fn pose_test() {
+    let skeleton = HavokSkeleton {
+        bone_names: vec!["n_root".to_owned(), "n_hara".to_owned(), "j_kosi".to_owned()],
+        parent_indices: vec![usize::MAX, 0, 1],
+        reference_pose: vec![translation(0., 0., 0.), translation(0., 1., 0.), translation(0., 1., 0.)],
+    };
+
+    let model = skeleton.to_model_space(&skeleton.reference_pose);
+    assert_near(&model[2].translation, &[0., 2., 0., 0.]);
+
+    let frames = [vec![translation(1., 0., 0.)], vec![translation(1., 0., 0.)]];
+    let mut builder = HavokAnimationContainerBuilder::new();
+    builder
+        .add_uncompressed_animation("skeleton", &[1], HavokAnimationBlendHint::Normal, 1., &frames)
+        .unwrap()
+        .add_uncompressed_animation("skeleton", &[1], HavokAnimationBlendHint::Additive, 1., &frames)
+        .unwrap();
+
+    let data = HavokBinaryTagFileWriter::write(&builder.build());
+    let root = HavokBinaryTagFileReader::read(&data);
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    // normal animation replaces bone
+    let pose = animation_container.bindings[0].sample_pose(&skeleton.reference_pose, 0.);
+    assert_near(&pose[1].translation, &[1., 0., 0., 0.]);
+    assert_near(&pose[2].translation, &[0., 1., 0., 0.]);
+
+    // additive animation is added on top of reference pose
+    let pose = animation_container.bindings[1].sample_pose(&skeleton.reference_pose, 0.);
+    assert_near(&pose[1].translation, &[1., 1., 0., 0.]);
+
+    let mut pose = skeleton.reference_pose.clone();
+    animation_container.bindings[0].blend_pose(&mut pose, 0., 0.5);
+    assert_near(&pose[1].translation, &[0.5, 0.5, 0., 0.]);
+}
+
+#[test]
+fn skeleton_merge_test() {
+    let mut base = HavokSkeleton {
+        bone_names: vec!["n_root".into(), "j_kao".into()],
+        parent_indices: vec![usize::MAX, 0],
+        reference_pose: vec![translation(0., 0., 0.), translation(0., 1., 0.)],
+    };
+    let face = HavokSkeleton {
+        bone_names: vec!["j_kao".into(), "j_ago".into()],
+        parent_indices: vec![usize::MAX, 0],
+        reference_pose: vec![translation(0., 1., 0.), translation(0., 0., 1.)],
+    };
+
+    base.merge(&face);
+
+    assert_eq!(base.bone_names, ["n_root", "j_kao", "j_ago"]);
+    assert_eq!(base.parent_indices, [usize::MAX, 0, 1]);
+    assert_eq!(base.reference_pose[2].translation, [0., 0., 1., 0.]);
+}
@@ -1,5 +1,5 @@
 use ffxiv_parser::Sklb;
-use havok_parser::{HavokAnimationContainer, HavokBinaryTagFileReader};
+use havok_parser::{HavokAnimationContainer, HavokReader};
 use sqpack::Result;
 use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
 
@@ -16,9 +16,9 @@ async fn skeleton_test() -> Result<()> {
     let sklb = Sklb::new(&pack, "chara/human/c0101/skeleton/base/b0001/skl_c0101b0001.sklb").await?;
     let hkx = sklb.hkx_data();
 
-    let root = HavokBinaryTagFileReader::read(hkx);
+    let root = HavokReader::read(hkx).unwrap();
     let raw_animation_container = root.find_object_by_type("hkaAnimationContainer");
-    let animation_container = HavokAnimationContainer::new(raw_animation_container);
+    let animation_container = HavokAnimationContainer::new(raw_animation_container).unwrap();
 
     let havok_skeleton = &animation_container.skeletons[0];
 
@@ -0,0 +1,198 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use ffxiv_parser::{Pap, Sklb};
+use havok_parser::{
+    HavokAnimationBlendHint, HavokAnimationContainer, HavokAnimationContainerBuilder, HavokBinaryTagFileReader, HavokBinaryTagFileWriter, HavokError,
+    HavokObject, HavokSkeleton, HavokTransform, HavokValue,
+};
+use sqpack::Result;
+use sqpack_extension::{ExtractedFileProviderWeb, SqPackReaderExtractedFile};
+
+// compares types, member values and object references; written objects must map one to one to the originals
+fn assert_object_eq(
+    a: &Rc<RefCell<HavokObject>>,
+    b: &Rc<RefCell<HavokObject>>,
+    visited: &mut HashMap<*const RefCell<HavokObject>, *const RefCell<HavokObject>>,
+) {
+    if let Some(&mapped) = visited.get(&Rc::as_ptr(a)) {
+        assert_eq!(mapped, Rc::as_ptr(b));
+        return;
+    }
+    visited.insert(Rc::as_ptr(a), Rc::as_ptr(b));
+
+    let (a, b) = (a.borrow(), b.borrow());
+    assert_eq!(a.object_type.name, b.object_type.name);
+
+    let a_members = a.object_type.members();
+    let b_members = b.object_type.members();
+    assert_eq!(a_members.len(), b_members.len());
+    for (index, (a_member, b_member)) in a_members.iter().zip(b_members.iter()).enumerate() {
+        assert_eq!(a_member.name, b_member.name);
+        assert_eq!(a_member.type_, b_member.type_);
+        assert_eq!(a_member.class_name, b_member.class_name);
+
+        match (a.value(index), b.value(index)) {
+            (Some(a), Some(b)) => assert_value_eq(a, b, visited),
+            (None, None) => {}
+            _ => panic!("member {} differs", a_member.name),
+        }
+    }
+}
+
+fn assert_value_eq(a: &HavokValue, b: &HavokValue, visited: &mut HashMap<*const RefCell<HavokObject>, *const RefCell<HavokObject>>) {
+    match (a, b) {
+        (HavokValue::Integer(a), HavokValue::Integer(b)) => assert_eq!(a, b),
+        (HavokValue::Real(a), HavokValue::Real(b)) => assert_eq!(a.to_bits(), b.to_bits()),
+        (HavokValue::String(a), HavokValue::String(b)) => assert_eq!(a, b),
+        (HavokValue::Vec(a), HavokValue::Vec(b)) => {
+            assert_eq!(
+                a.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                b.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+            )
+        }
+        (HavokValue::Array(a), HavokValue::Array(b)) => {
+            assert_eq!(a.len(), b.len());
+            for (a, b) in a.iter().zip(b.iter()) {
+                assert_value_eq(a, b, visited);
+            }
+        }
+        (HavokValue::Object(a), HavokValue::Object(b)) => assert_object_eq(a, b, visited),
+        (HavokValue::ObjectReference(a), HavokValue::ObjectReference(b)) => assert_eq!(a, b),
+        _ => panic!("value kinds differ"),
+    }
+}
+
+fn assert_skeleton_eq(a: &HavokSkeleton, b: &HavokSkeleton) {
+    assert_eq!(a.bone_names, b.bone_names);
+    assert_eq!(a.parent_indices, b.parent_indices);
+    assert_eq!(a.reference_pose.len(), b.reference_pose.len());
+    for (a, b) in a.reference_pose.iter().zip(b.reference_pose.iter()) {
+        assert_eq!(a.translation, b.translation);
+        assert_eq!(a.rotation, b.rotation);
+        assert_eq!(a.scale, b.scale);
+    }
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn build_test() {
+    let skeleton = HavokSkeleton {
+        bone_names: vec!["n_root".to_owned(), "n_hara".to_owned()],
+        parent_indices: vec![usize::MAX, 0],
+        reference_pose: vec![
+            HavokTransform::identity(),
+            HavokTransform::from_trs([0., 1., 0., 0.], [0., 0., 0., 1.], [1., 1., 1., 1.]),
+        ],
+    };
+    let frames = [
+        vec![HavokTransform::identity()],
+        vec![HavokTransform::from_trs([2., 0., 0., 0.], [0., 0., 0., 1.], [1., 1., 1., 1.])],
+    ];
+
+    let mut builder = HavokAnimationContainerBuilder::new();
+    builder
+        .add_skeleton("skeleton", &skeleton)
+        .add_uncompressed_animation("skeleton", &[1], HavokAnimationBlendHint::Normal, 1., &frames)
+        .unwrap();
+    let built = builder.build();
+    let data = HavokBinaryTagFileWriter::write(&built);
+
+    let root = HavokBinaryTagFileReader::read(&data);
+    assert_object_eq(&built.object(), &root.object(), &mut HashMap::new());
+
+    let animation_container = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    assert_skeleton_eq(&animation_container.skeletons[0], &skeleton);
+
+    let binding = &animation_container.bindings[0];
+    assert_eq!(binding.transform_track_to_bone_indices, [1]);
+    assert_eq!(binding.animation.duration(), 1.);
+    assert_eq!(binding.animation.sample(500.)[0].translation, [1., 0., 0., 0.]);
+}
+
+#[test]
+fn track_count_mismatch_test() {
+    let frames = [
+        vec![HavokTransform::identity()],
+        vec![HavokTransform::identity(), HavokTransform::identity()],
+    ];
+
+    let mut builder = HavokAnimationContainerBuilder::new();
+    let result = builder.add_uncompressed_animation("skeleton", &[1], HavokAnimationBlendHint::Normal, 1., &frames);
+
+    assert!(matches!(result, Err(HavokError::TrackCountMismatch { expected: 1, actual: 2 })));
+    assert!(
+        HavokAnimationContainer::new(builder.build().find_object_by_type("hkaAnimationContainer"))
+            .unwrap()
+            .bindings
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn round_trip_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let sklb = Sklb::new(&pack, "chara/human/c0101/skeleton/base/b0001/skl_c0101b0001.sklb").await?;
+
+    let root = HavokBinaryTagFileReader::read(sklb.hkx_data());
+    let data = HavokBinaryTagFileWriter::write(&root);
+    let written_root = HavokBinaryTagFileReader::read(&data);
+
+    assert_object_eq(&root.object(), &written_root.object(), &mut HashMap::new());
+
+    let original = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+    let written = HavokAnimationContainer::new(written_root.find_object_by_type("hkaAnimationContainer")).unwrap();
+
+    assert_eq!(original.skeletons.len(), written.skeletons.len());
+    assert_skeleton_eq(&original.skeletons[0], &written.skeletons[0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn spline_round_trip_test() -> Result<()> {
+    let _ = pretty_env_logger::formatted_timed_builder()
+        .filter(Some("sqpack"), log::LevelFilter::Debug)
+        .try_init();
+
+    let provider = ExtractedFileProviderWeb::new("https://ffxiv-data.dlunch.net/compressed/all/");
+    let pack = SqPackReaderExtractedFile::new(provider);
+
+    let pap = Pap::new(&pack, "chara/human/c0101/animation/a0001/bt_common/resident/idle.pap").await?;
+
+    let root = HavokBinaryTagFileReader::read(pap.hkx_data());
+    let data = HavokBinaryTagFileWriter::write(&root);
+    let written_root = HavokBinaryTagFileReader::read(&data);
+
+    assert_object_eq(&root.object(), &written_root.object(), &mut HashMap::new());
+
+    let original = HavokAnimationContainer::new(root.find_object_by_type("hkaAnimationContainer")).unwrap();
+    let written = HavokAnimationContainer::new(written_root.find_object_by_type("hkaAnimationContainer")).unwrap();
+    let raw_container = written_root.find_object_by_type("hkaAnimationContainer");
+    let animation_types = raw_container
+        .borrow()
+        .get("animations")
+        .as_array()
+        .iter()
+        .map(|x| x.as_object().borrow().object_type.name.clone())
+        .collect::<Vec<_>>();
+    assert!(!animation_types.is_empty());
+    assert!(animation_types.iter().all(|x| &**x == "hkaSplineCompressedAnimation"));
+
+    let time = original.bindings[0].animation.duration() * 500.;
+    for (original, written) in original.bindings.iter().zip(written.bindings.iter()) {
+        for (a, b) in original.animation.sample(time).iter().zip(written.animation.sample(time).iter()) {
+            assert_eq!(a.translation, b.translation);
+            assert_eq!(a.rotation, b.rotation);
+            assert_eq!(a.scale, b.scale);
+        }
+    }
+
+    Ok(())
+}
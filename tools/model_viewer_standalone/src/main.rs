@@ -62,6 +62,6 @@ async fn setup(world: &mut World) {
     equipments.insert(ModelPart::Dwn, Equipment::new(6016, 1, 0));
     equipments.insert(ModelPart::Sho, Equipment::new(6016, 1, 0));
 
-    let customization = Customization::new(BodyId::AuRaFemale, 1, 1, 1, 1, 1);
+    let customization = Customization::new(BodyId::AuRaFemale, 1, 1, 1, 1, 1).with_tail(1);
     Character::load(world, &*package, &context, customization, equipments).await.unwrap();
 }